@@ -1,6 +1,7 @@
+use crate::algorithm::nearest_neighbor::NearestNeighborQuery;
 use crate::point::{max_inline, Point, PointExt};
-use crate::{Envelope, RTreeObject};
-use num_traits::{Bounded, One, Zero};
+use crate::{Envelope, GeometryDistance, RTreeObject};
+use num_traits::{Bounded, Float, NumCast, One, Signed, Zero};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -116,6 +117,70 @@ where
             self.min_point(point).sub(point).length_2()
         }
     }
+
+    /// Returns the squared distance between a point and the farthest point of the AABB.
+    ///
+    /// This serves as an upper bound of the distance to all objects contained within the AABB.
+    pub(crate) fn max_distance_2(&self, point: &P) -> P::Scalar {
+        let farthest = P::generate(|i| {
+            let to_lower = point.nth(i) - self.lower.nth(i);
+            let to_upper = self.upper.nth(i) - point.nth(i);
+            max_inline(to_lower.abs(), to_upper.abs())
+        });
+        farthest.length_2()
+    }
+
+    /// Returns the squared distance between the closest points of two AABBs.
+    ///
+    /// Returns zero if both AABBs intersect.
+    pub(crate) fn distance_2_to_aabb(&self, other: &Self) -> P::Scalar {
+        let zero = P::Scalar::zero();
+        let gap = P::generate(|i| {
+            let below = other.lower.nth(i) - self.upper.nth(i);
+            let above = self.lower.nth(i) - other.upper.nth(i);
+            max_inline(max_inline(below, above), zero)
+        });
+        gap.length_2()
+    }
+}
+
+impl<P, T> NearestNeighborQuery<T> for AABB<P>
+where
+    P: Point,
+    T: GeometryDistance<AABB<P>> + RTreeObject<Envelope = AABB<P>>,
+{
+    fn envelope_distance_2(&self, envelope: &AABB<P>) -> P::Scalar {
+        self.distance_2_to_aabb(envelope)
+    }
+
+    fn object_distance_2(&self, object: &T) -> P::Scalar {
+        object.geometry_distance_2(self)
+    }
+}
+
+impl<P> AABB<P>
+where
+    P: Point,
+    P::Scalar: Float,
+{
+    /// Splits a longitude / latitude box crossing the antimeridian into two AABBs.
+    ///
+    /// The box crosses the antimeridian if `lower`'s longitude is larger than `upper`'s
+    /// longitude. Otherwise, the box is returned together with an empty AABB.
+    pub(crate) fn split_at_antimeridian(lower: P, upper: P) -> (Self, Self) {
+        if lower.nth(0) <= upper.nth(0) {
+            return (AABB::from_corners(lower, upper), AABB::new_empty());
+        }
+        let antimeridian: P::Scalar = NumCast::from(180.0).unwrap();
+        let mut east_upper = upper.clone();
+        *east_upper.nth_mut(0) = antimeridian;
+        let mut west_lower = lower.clone();
+        *west_lower.nth_mut(0) = -antimeridian;
+        (
+            AABB::from_corners(lower, east_upper),
+            AABB::from_corners(west_lower, upper),
+        )
+    }
 }
 
 impl<P> Envelope for AABB<P>
@@ -293,6 +358,29 @@ mod test {
         assert_eq!(aabb, AABB::from_corners((3., 3., 3.), (4., 4., 4.)));
     }
 
+    #[test]
+    fn test_distance_2_to_aabb() {
+        let aabb = AABB::from_corners([0.0, 0.0], [1.0, 1.0]);
+
+        let overlapping = AABB::from_corners([0.5, 0.5], [2.0, 2.0]);
+        assert_eq!(aabb.distance_2_to_aabb(&overlapping), 0.0);
+        let touching = AABB::from_corners([1.0, 0.5], [2.0, 2.0]);
+        assert_eq!(aabb.distance_2_to_aabb(&touching), 0.0);
+        let beside = AABB::from_corners([3.0, 0.5], [4.0, 0.7]);
+        assert_eq!(aabb.distance_2_to_aabb(&beside), 4.0);
+        assert_eq!(beside.distance_2_to_aabb(&aabb), 4.0);
+        let diagonal = AABB::from_corners([-3.0, -4.0], [-2.0, -3.0]);
+        assert_eq!(aabb.distance_2_to_aabb(&diagonal), 4.0 + 9.0);
+    }
+
+    #[test]
+    fn test_max_distance_2() {
+        let aabb = AABB::from_corners([0.0, 0.0], [2.0, 1.0]);
+        assert_eq!(aabb.max_distance_2(&[0.5, 0.5]), 1.5 * 1.5 + 0.5 * 0.5);
+        assert_eq!(aabb.max_distance_2(&[-1.0, 3.0]), 9.0 + 9.0);
+        assert_eq!(aabb.max_distance_2(&[2.0, 1.0]), 4.0 + 1.0);
+    }
+
     #[test]
     fn test_is_empty() {
         let empty = AABB::<[f32; 2]>::new_empty();
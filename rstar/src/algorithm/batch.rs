@@ -0,0 +1,278 @@
+use crate::algorithm::iterators::NearestNeighborDistance2Iterator;
+use crate::algorithm::nearest_neighbor::nearest_neighbor_within_distance_2;
+use crate::node::{ParentNode, RTreeNode};
+use crate::object::Distance;
+use crate::{Envelope, Point, PointDistance};
+
+#[cfg(not(test))]
+use alloc::{vec, vec::Vec};
+use num_traits::Bounded;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Number of spatially sorted queries that are processed by a single task of
+/// the parallel batch queries.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 256;
+
+/// Returns the indices of `points` ordered such that nearby points are likely to be
+/// next to each other.
+///
+/// The points are recursively split at their median, cycling through all axes,
+/// similar to the partitioning used when bulk loading a tree.
+pub fn sort_spatially<P: Point>(points: &[P]) -> Vec<usize> {
+    fn sort_recursive<P: Point>(indices: &mut [usize], points: &[P], axis: usize) {
+        if indices.len() <= 2 {
+            return;
+        }
+        let mid = indices.len() / 2;
+        indices.select_nth_unstable_by(mid, |l, r| {
+            let (l, r) = (points[*l].nth(axis), points[*r].nth(axis));
+            // Coordinates which are not comparable (e.g. NaN) are placed after all others
+            l.partial_cmp(&r).unwrap_or_else(|| {
+                let is_incomparable = |value: P::Scalar| value.partial_cmp(&value).is_none();
+                is_incomparable(l).cmp(&is_incomparable(r))
+            })
+        });
+        let next_axis = (axis + 1) % P::DIMENSIONS;
+        let (left, right) = indices.split_at_mut(mid);
+        sort_recursive(left, points, next_axis);
+        sort_recursive(right, points, next_axis);
+    }
+
+    let mut indices: Vec<usize> = (0..points.len()).collect();
+    sort_recursive(&mut indices, points, 0);
+    indices
+}
+
+/// Returns the nearest neighbor of each query point in the order given by `indices`.
+///
+/// Each query is searched from the root. The neighbor found for the previous query is
+/// used to bound the search of the next one, which prunes most nodes if consecutive
+/// queries are close to each other.
+fn nearest_neighbors_in_order<'a, T>(
+    root: &'a ParentNode<T>,
+    query_points: &[<T::Envelope as Envelope>::Point],
+    indices: &[usize],
+) -> Vec<Option<&'a T>>
+where
+    T: PointDistance,
+{
+    let mut result = Vec::with_capacity(indices.len());
+    let mut previous: Option<&'a T> = None;
+    for &index in indices {
+        let query_point = &query_points[index];
+        let max_distance_2: Distance<T> = previous
+            .map(|neighbor| neighbor.distance_2(query_point))
+            .unwrap_or_else(Bounded::max_value);
+        // The bounded search may in rare cases return None due to rounding issues.
+        // The iterator will still work, though.
+        let neighbor = nearest_neighbor_within_distance_2(root, query_point, max_distance_2)
+            .or_else(|| NearestNeighborDistance2Iterator::new(root, query_point.clone()).next())
+            .map(|(neighbor, _)| neighbor);
+        previous = neighbor.or(previous);
+        result.push(neighbor);
+    }
+    result
+}
+
+/// Returns the nearest neighbor of each query point, in the order of `query_points`.
+pub fn nearest_neighbors_batch<'a, T>(
+    root: &'a ParentNode<T>,
+    query_points: &[<T::Envelope as Envelope>::Point],
+) -> Vec<Option<&'a T>>
+where
+    T: PointDistance,
+{
+    let indices = sort_spatially(query_points);
+    let mut result = vec![None; query_points.len()];
+    for (index, neighbor) in
+        indices
+            .iter()
+            .zip(nearest_neighbors_in_order(root, query_points, &indices))
+    {
+        result[*index] = neighbor;
+    }
+    result
+}
+
+/// Parallel variant of [nearest_neighbors_batch].
+#[cfg(feature = "rayon")]
+pub fn par_nearest_neighbors_batch<'a, T>(
+    root: &'a ParentNode<T>,
+    query_points: &[<T::Envelope as Envelope>::Point],
+) -> Vec<Option<&'a T>>
+where
+    T: PointDistance + Sync,
+    T::Envelope: Sync,
+    <T::Envelope as Envelope>::Point: Sync,
+{
+    let indices = sort_spatially(query_points);
+    let chunks: Vec<_> = indices
+        .par_chunks(PARALLEL_CHUNK_SIZE)
+        .map(|chunk| nearest_neighbors_in_order(root, query_points, chunk))
+        .collect();
+    let mut result = vec![None; query_points.len()];
+    for (index, neighbor) in indices.iter().zip(chunks.into_iter().flatten()) {
+        result[*index] = neighbor;
+    }
+    result
+}
+
+/// Locates all elements containing the points referenced by `indices`.
+///
+/// All queries share a single traversal: a child node is only visited once,
+/// together with all query points contained in its envelope. The elements found
+/// for `query_points[i]` are pushed to `result[i]`.
+fn locate_all_at_points_recursive<'a, T>(
+    node: &'a ParentNode<T>,
+    query_points: &[<T::Envelope as Envelope>::Point],
+    indices: &[usize],
+    result: &mut [Vec<&'a T>],
+) where
+    T: PointDistance,
+{
+    let mut child_indices = Vec::new();
+    for child in &node.children {
+        match child {
+            RTreeNode::Parent(ref data) => {
+                child_indices.clear();
+                child_indices.extend(
+                    indices
+                        .iter()
+                        .filter(|index| data.envelope.contains_point(&query_points[**index])),
+                );
+                if !child_indices.is_empty() {
+                    locate_all_at_points_recursive(data, query_points, &child_indices, result);
+                }
+            }
+            RTreeNode::Leaf(ref t) => {
+                for &index in indices {
+                    if t.contains_point(&query_points[index]) {
+                        result[index].push(t);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns all elements containing each query point, in the order of `query_points`.
+pub fn locate_all_at_points<'a, T>(
+    root: &'a ParentNode<T>,
+    query_points: &[<T::Envelope as Envelope>::Point],
+) -> Vec<Vec<&'a T>>
+where
+    T: PointDistance,
+{
+    let indices = sort_spatially(query_points);
+    let mut result = vec![Vec::new(); query_points.len()];
+    locate_all_at_points_recursive(root, query_points, &indices, &mut result);
+    result
+}
+
+/// Parallel variant of [locate_all_at_points].
+#[cfg(feature = "rayon")]
+pub fn par_locate_all_at_points<'a, T>(
+    root: &'a ParentNode<T>,
+    query_points: &[<T::Envelope as Envelope>::Point],
+) -> Vec<Vec<&'a T>>
+where
+    T: PointDistance + Sync,
+    T::Envelope: Sync,
+    <T::Envelope as Envelope>::Point: Sync,
+{
+    let indices = sort_spatially(query_points);
+    let chunks: Vec<_> = indices
+        .par_chunks(PARALLEL_CHUNK_SIZE)
+        .map(|chunk| {
+            let chunk_points: Vec<_> = chunk
+                .iter()
+                .map(|index| query_points[*index].clone())
+                .collect();
+            let chunk_indices: Vec<_> = (0..chunk.len()).collect();
+            let mut chunk_result = vec![Vec::new(); chunk.len()];
+            locate_all_at_points_recursive(root, &chunk_points, &chunk_indices, &mut chunk_result);
+            chunk_result
+        })
+        .collect();
+    let mut result = vec![Vec::new(); query_points.len()];
+    for (index, located) in indices.iter().zip(chunks.into_iter().flatten()) {
+        result[*index] = located;
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::sort_spatially;
+    use crate::object::PointDistance;
+    use crate::test_utilities::*;
+    use crate::RTree;
+
+    #[test]
+    fn test_sort_spatially() {
+        let points = create_random_points(1000, SEED_1);
+        let mut indices = sort_spatially(&points);
+        indices.sort_unstable();
+        assert_eq!(indices, (0..points.len()).collect::<Vec<_>>());
+        assert!(sort_spatially::<[f64; 2]>(&[]).is_empty());
+
+        let mut points = create_random_points(100, SEED_2);
+        points[10] = [f64::NAN, 0.5];
+        points[20] = [0.5, f64::NAN];
+        let mut indices = sort_spatially(&points);
+        indices.sort_unstable();
+        assert_eq!(indices, (0..points.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_nearest_neighbors_batch() {
+        let tree = RTree::bulk_load(create_random_points(1000, SEED_1));
+        let query_points = create_random_points(500, SEED_2);
+        let result = tree.nearest_neighbors_batch(&query_points);
+        assert_eq!(result.len(), query_points.len());
+        for (query_point, neighbor) in query_points.iter().zip(result) {
+            let expected = tree.nearest_neighbor(*query_point).unwrap();
+            assert_eq!(
+                neighbor.unwrap().distance_2(query_point),
+                expected.distance_2(query_point)
+            );
+        }
+
+        let empty: RTree<[f64; 2]> = RTree::new();
+        assert_eq!(
+            empty.nearest_neighbors_batch(&query_points[..3]),
+            vec![None; 3]
+        );
+    }
+
+    #[test]
+    fn test_locate_all_at_points() {
+        let tree = RTree::bulk_load(create_random_rectangles(500, SEED_1));
+        let query_points = create_random_points(500, SEED_2);
+        let result = tree.locate_all_at_points(&query_points);
+        assert_eq!(result.len(), query_points.len());
+        for (query_point, mut located) in query_points.iter().zip(result) {
+            let mut expected: Vec<_> = tree.locate_all_at_point(*query_point).collect();
+            expected.sort_by(|l, r| l.lower().partial_cmp(&r.lower()).unwrap());
+            located.sort_by(|l, r| l.lower().partial_cmp(&r.lower()).unwrap());
+            assert_eq!(located, expected);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_batch_queries() {
+        let tree = RTree::bulk_load(create_random_rectangles(500, SEED_1));
+        let query_points = create_random_points(2000, SEED_2);
+        assert_eq!(
+            tree.par_nearest_neighbors_batch(&query_points),
+            tree.nearest_neighbors_batch(&query_points)
+        );
+        assert_eq!(
+            tree.par_locate_all_at_points(&query_points),
+            tree.locate_all_at_points(&query_points)
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use crate::algorithm::nearest_neighbor::{RTreeNodeDistanceWrapper, SmallHeap};
+use crate::node::{ParentNode, RTreeNode};
+use crate::RTreeObject;
+
+#[cfg(doc)]
+use crate::RTree;
+
+/// Defines the order of a best-first search through an r-tree.
+///
+/// A best-first search visits all elements of an r-tree in ascending order of a
+/// user defined _score_, e.g. a weighted or time dependent distance. The search is
+/// guided by a lower bound of the scores of all objects contained in a node's envelope.
+/// A tighter bound will result in fewer visited nodes.
+///
+/// A search is issued by calling [RTree::best_first_iter].
+///
+/// # Notes
+/// - [lower_bound](Self::lower_bound) **must** never be larger than the
+///   [score](Self::score) of any object contained within the envelope. Otherwise, the
+///   elements will not be returned in ascending order.
+/// - Comparing two keys **must** never fail, i.e. keys must not be `NaN`.
+///
+/// # Example
+/// ```
+/// use rstar::{AABB, BestFirstFunction, RTree};
+///
+/// // Orders points by their x coordinate, regardless of their y coordinate
+/// struct LeftmostFirst;
+///
+/// impl BestFirstFunction<[f64; 2]> for LeftmostFirst {
+///     type Key = f64;
+///
+///     fn lower_bound(&self, envelope: &AABB<[f64; 2]>) -> f64 {
+///         envelope.lower()[0]
+///     }
+///
+///     fn score(&self, point: &[f64; 2]) -> f64 {
+///         point[0]
+///     }
+/// }
+///
+/// let tree = RTree::bulk_load(vec![[2.0, 0.0], [0.0, 5.0], [1.0, -3.0]]);
+/// let ordered: Vec<_> = tree.best_first_iter(LeftmostFirst).collect();
+/// assert_eq!(
+///     ordered,
+///     vec![(&[0.0, 5.0], 0.0), (&[1.0, -3.0], 1.0), (&[2.0, 0.0], 2.0)]
+/// );
+/// ```
+pub trait BestFirstFunction<T>
+where
+    T: RTreeObject,
+{
+    /// The ordered key used to rank objects and nodes.
+    type Key: PartialOrd;
+
+    /// Returns a lower bound of the score of any object contained within `envelope`.
+    fn lower_bound(&self, envelope: &T::Envelope) -> Self::Key;
+
+    /// Returns the score of an object.
+    fn score(&self, object: &T) -> Self::Key;
+}
+
+/// A best-first search that may prune nodes and objects.
+///
+/// This generalizes [BestFirstFunction] for the searches built on [BestFirstSearch], e.g.
+/// nearest neighbor queries. Returning `None` skips a node with all contained objects or
+/// a single object.
+pub(crate) trait PrunedBestFirstFunction<T>
+where
+    T: RTreeObject,
+{
+    /// The ordered key used to rank objects and nodes.
+    type Key: PartialOrd;
+
+    /// Returns a lower bound of the key of any object contained within `envelope`.
+    fn node_key(&self, envelope: &T::Envelope) -> Option<Self::Key>;
+
+    /// Returns the key of an object.
+    fn object_key(&self, object: &T) -> Option<Self::Key>;
+}
+
+/// Adapts a [BestFirstFunction], which never prunes any node or object.
+pub(crate) struct Unpruned<F>(F);
+
+impl<T, F> PrunedBestFirstFunction<T> for Unpruned<F>
+where
+    T: RTreeObject,
+    F: BestFirstFunction<T>,
+{
+    type Key = F::Key;
+
+    fn node_key(&self, envelope: &T::Envelope) -> Option<Self::Key> {
+        Some(self.0.lower_bound(envelope))
+    }
+
+    fn object_key(&self, object: &T) -> Option<Self::Key> {
+        Some(self.0.score(object))
+    }
+}
+
+/// Visits all objects not pruned by a [PrunedBestFirstFunction] in ascending order of
+/// their key.
+pub(crate) struct BestFirstSearch<'a, T, F, K>
+where
+    T: RTreeObject + 'a,
+    K: PartialOrd,
+{
+    nodes: SmallHeap<RTreeNodeDistanceWrapper<'a, T, K>>,
+    function: F,
+}
+
+impl<'a, T, F> BestFirstSearch<'a, T, F, F::Key>
+where
+    T: RTreeObject,
+    F: PrunedBestFirstFunction<T>,
+{
+    pub(crate) fn new(root: &'a ParentNode<T>, function: F) -> Self {
+        let mut result = BestFirstSearch {
+            nodes: SmallHeap::new(),
+            function,
+        };
+        if !root.children.is_empty() && result.function.node_key(&root.envelope).is_some() {
+            result.extend_heap(&root.children);
+        }
+        result
+    }
+
+    fn extend_heap(&mut self, children: &'a [RTreeNode<T>]) {
+        let &mut BestFirstSearch {
+            ref mut nodes,
+            ref function,
+        } = self;
+        nodes.extend(children.iter().filter_map(|child| {
+            // Pruned children are never pushed onto the heap
+            let distance = match child {
+                RTreeNode::Parent(ref data) => function.node_key(&data.envelope)?,
+                RTreeNode::Leaf(ref t) => function.object_key(t)?,
+            };
+            Some(RTreeNodeDistanceWrapper {
+                node: child,
+                distance,
+            })
+        }));
+    }
+}
+
+impl<'a, T, F> Iterator for BestFirstSearch<'a, T, F, F::Key>
+where
+    T: RTreeObject,
+    F: PrunedBestFirstFunction<T>,
+{
+    type Item = (&'a T, F::Key);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(current) = self.nodes.pop() {
+            match current {
+                RTreeNodeDistanceWrapper {
+                    node: RTreeNode::Parent(ref data),
+                    ..
+                } => {
+                    self.extend_heap(&data.children);
+                }
+                RTreeNodeDistanceWrapper {
+                    node: RTreeNode::Leaf(ref t),
+                    distance,
+                } => {
+                    return Some((t, distance));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Iterator returned by [`RTree::best_first_iter`].
+///
+/// Yields `(object, score)` tuples in ascending order of their score.
+pub struct BestFirstIterator<'a, T, F>
+where
+    T: RTreeObject + 'a,
+    F: BestFirstFunction<T>,
+{
+    search: BestFirstSearch<'a, T, Unpruned<F>, F::Key>,
+}
+
+impl<'a, T, F> BestFirstIterator<'a, T, F>
+where
+    T: RTreeObject,
+    F: BestFirstFunction<T>,
+{
+    pub(crate) fn new(root: &'a ParentNode<T>, function: F) -> Self {
+        BestFirstIterator {
+            search: BestFirstSearch::new(root, Unpruned(function)),
+        }
+    }
+}
+
+impl<'a, T, F> Iterator for BestFirstIterator<'a, T, F>
+where
+    T: RTreeObject,
+    F: BestFirstFunction<T>,
+{
+    type Item = (&'a T, F::Key);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.search.next()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BestFirstFunction;
+    use crate::object::PointDistance;
+    use crate::primitives::GeomWithData;
+    use crate::test_utilities::*;
+    use crate::{RTree, AABB};
+
+    /// Orders weighted points by their squared distance to a query point multiplied by
+    /// their weight. All weights lie within `[min_weight, 1.0]`.
+    struct WeightedDistance {
+        query_point: [f64; 2],
+        min_weight: f64,
+    }
+
+    impl BestFirstFunction<GeomWithData<[f64; 2], f64>> for WeightedDistance {
+        type Key = f64;
+
+        fn lower_bound(&self, envelope: &AABB<[f64; 2]>) -> f64 {
+            envelope.distance_2(&self.query_point) * self.min_weight
+        }
+
+        fn score(&self, point: &GeomWithData<[f64; 2], f64>) -> f64 {
+            point.geom().distance_2(&self.query_point) * point.data
+        }
+    }
+
+    #[test]
+    fn test_best_first_iter() {
+        let weights = create_random_points(1000, SEED_2);
+        let points: Vec<_> = create_random_points(1000, SEED_1)
+            .into_iter()
+            .zip(weights)
+            .map(|(point, [weight, _])| GeomWithData::new(point, 0.1 + weight * 0.9))
+            .collect();
+        let tree = RTree::bulk_load(points.clone());
+
+        for query_point in create_random_points(10, SEED_2) {
+            let function = WeightedDistance {
+                query_point,
+                min_weight: 0.1,
+            };
+            let mut expected: Vec<_> = points.iter().map(|p| function.score(p)).collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let result: Vec<_> = tree.best_first_iter(function).collect();
+            assert_eq!(result.len(), points.len());
+            for ((point, score), expected_score) in result.iter().zip(&expected) {
+                assert_eq!(score, expected_score);
+                assert_eq!(point.geom().distance_2(&query_point) * point.data, *score);
+            }
+        }
+    }
+
+    #[test]
+    fn test_best_first_iter_empty() {
+        let tree: RTree<GeomWithData<[f64; 2], f64>> = RTree::new();
+        let function = WeightedDistance {
+            query_point: [0.0, 0.0],
+            min_weight: 1.0,
+        };
+        assert_eq!(tree.best_first_iter(function).count(), 0);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::algorithm::nearest_neighbor::{NearestNeighborQuery, NodeDistanceWrapper};
+use crate::node::{ParentNode, RTreeNode};
+use crate::object::Distance;
+use crate::{Point, RTreeObject, AABB};
+
+use alloc::collections::BinaryHeap;
+#[cfg(not(test))]
+use alloc::vec::Vec;
+
+/// A heap entry ordering pairs of nodes by ascending distance.
+type NodePairDistanceWrapper<'a, T, U> =
+    NodeDistanceWrapper<(&'a RTreeNode<T>, &'a RTreeNode<U>), Distance<T>>;
+
+struct ClosestPairsSearch<'a, T, U>
+where
+    T: RTreeObject + 'a,
+    U: RTreeObject + 'a,
+{
+    heap: BinaryHeap<NodePairDistanceWrapper<'a, T, U>>,
+    /// The smallest object distances found so far, in ascending order.
+    /// Contains at most `k` elements.
+    best_distances: Vec<Distance<T>>,
+    k: usize,
+}
+
+impl<'a, T, U, P> ClosestPairsSearch<'a, T, U>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>> + NearestNeighborQuery<U>,
+    U: RTreeObject<Envelope = AABB<P>>,
+{
+    fn push(&mut self, node1: &'a RTreeNode<T>, node2: &'a RTreeNode<U>) {
+        let distance = match (node1, node2) {
+            (RTreeNode::Leaf(t), RTreeNode::Leaf(u)) => t.object_distance_2(u),
+            (RTreeNode::Leaf(t), RTreeNode::Parent(p)) => t.envelope_distance_2(&p.envelope()),
+            (RTreeNode::Parent(p), _) => p.envelope().distance_2_to_aabb(&node2.envelope()),
+        };
+        if let Some(max_distance) = self.best_distances.get(self.k - 1) {
+            if distance > *max_distance {
+                // At least k closer pairs are known already
+                return;
+            }
+        }
+        if let (RTreeNode::Leaf(_), RTreeNode::Leaf(_)) = (node1, node2) {
+            let index = self.best_distances.partition_point(|d| *d <= distance);
+            self.best_distances.insert(index, distance);
+            self.best_distances.truncate(self.k);
+        }
+        self.heap.push(NodeDistanceWrapper {
+            node: (node1, node2),
+            distance,
+        });
+    }
+}
+
+pub fn closest_pairs<'a, T, U, P>(
+    root1: &'a ParentNode<T>,
+    root2: &'a ParentNode<U>,
+    k: usize,
+) -> Vec<(&'a T, &'a U, Distance<T>)>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>> + NearestNeighborQuery<U>,
+    U: RTreeObject<Envelope = AABB<P>>,
+{
+    let mut result = Vec::new();
+    if k == 0 {
+        return result;
+    }
+    let mut search = ClosestPairsSearch {
+        heap: BinaryHeap::new(),
+        best_distances: Vec::new(),
+        k,
+    };
+    for child1 in root1.children() {
+        for child2 in root2.children() {
+            search.push(child1, child2);
+        }
+    }
+
+    while let Some(NodeDistanceWrapper {
+        node: (node1, node2),
+        distance,
+    }) = search.heap.pop()
+    {
+        match (node1, node2) {
+            (RTreeNode::Leaf(t), RTreeNode::Leaf(u)) => {
+                result.push((t, u, distance));
+                if result.len() == k {
+                    break;
+                }
+            }
+            (RTreeNode::Leaf(_), RTreeNode::Parent(p)) => {
+                for child in p.children() {
+                    search.push(node1, child);
+                }
+            }
+            (RTreeNode::Parent(p), RTreeNode::Leaf(_)) => {
+                for child in p.children() {
+                    search.push(child, node2);
+                }
+            }
+            (RTreeNode::Parent(p1), RTreeNode::Parent(p2)) => {
+                for child1 in p1.children() {
+                    for child2 in p2.children() {
+                        search.push(child1, child2);
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::object::PointDistance;
+    use crate::primitives::Line;
+    use crate::test_utilities::*;
+    use crate::RTree;
+
+    #[test]
+    fn test_closest_pairs() {
+        let points1 = create_random_points(300, SEED_1);
+        let points2 = create_random_points(200, SEED_2);
+        let tree1 = RTree::bulk_load(points1.clone());
+        let tree2 = RTree::bulk_load(points2.clone());
+
+        let mut distances_brute_force = Vec::new();
+        for point1 in &points1 {
+            for point2 in &points2 {
+                distances_brute_force.push(point1.distance_2(point2));
+            }
+        }
+        distances_brute_force.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        for k in [0, 1, 10, 100] {
+            let pairs = tree1.closest_pairs(&tree2, k);
+            let distances: Vec<_> = pairs.iter().map(|(_, _, d)| *d).collect();
+            assert_eq!(distances, distances_brute_force[..k]);
+            for (point1, point2, distance_2) in pairs {
+                assert_eq!(point1.distance_2(point2), distance_2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_closest_pairs_of_lines() {
+        let lines1 = create_random_lines(100, SEED_1);
+        let lines2 = create_random_lines(50, SEED_2);
+        let tree1 = RTree::bulk_load(lines1.clone());
+        let tree2: RTree<Line<[f64; 2]>> = RTree::bulk_load(lines2.clone());
+
+        let mut distances_brute_force = Vec::new();
+        for line1 in &lines1 {
+            for line2 in &lines2 {
+                distances_brute_force.push(line1.distance_2_to_line(line2));
+            }
+        }
+        distances_brute_force.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let distances: Vec<_> = tree1
+            .closest_pairs(&tree2, 20)
+            .into_iter()
+            .map(|(_, _, d)| d)
+            .collect();
+        assert_eq!(distances, distances_brute_force[..20]);
+    }
+
+    #[test]
+    fn test_closest_pairs_more_than_size() {
+        let points1 = create_random_points(10, SEED_1);
+        let points2 = create_random_points(5, SEED_2);
+        let tree1 = RTree::bulk_load(points1);
+        let tree2 = RTree::bulk_load(points2);
+        assert_eq!(tree1.closest_pairs(&tree2, 100).len(), 50);
+
+        let empty: RTree<[f64; 2]> = RTree::new();
+        assert!(tree1.closest_pairs(&empty, 3).is_empty());
+        assert!(empty.closest_pairs(&tree1, 3).is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::algorithm::best_first::{BestFirstSearch, PrunedBestFirstFunction};
+use crate::node::ParentNode;
+use crate::object::Distance;
+use crate::{Envelope, Point, PointDistance, RTreeObject, AABB};
+use core::cmp::Reverse;
+
+#[cfg(doc)]
+use crate::RTree;
+
+/// Orders objects by descending distance to a query point.
+pub(crate) struct FarthestNeighborFunction<T>
+where
+    T: RTreeObject,
+{
+    query_point: <T::Envelope as Envelope>::Point,
+}
+
+impl<T, P> PrunedBestFirstFunction<T> for FarthestNeighborFunction<T>
+where
+    P: Point,
+    T: PointDistance + RTreeObject<Envelope = AABB<P>>,
+{
+    type Key = Reverse<P::Scalar>;
+
+    fn node_key(&self, envelope: &AABB<P>) -> Option<Self::Key> {
+        // The maximum distance to an envelope is an upper bound of the
+        // distances of all contained objects
+        Some(Reverse(envelope.max_distance_2(&self.query_point)))
+    }
+
+    fn object_key(&self, object: &T) -> Option<Self::Key> {
+        Some(Reverse(object.distance_2(&self.query_point)))
+    }
+}
+
+impl<'a, T, P> FarthestNeighborDistance2Iterator<'a, T>
+where
+    P: Point,
+    T: PointDistance + RTreeObject<Envelope = AABB<P>>,
+{
+    pub(crate) fn new(root: &'a ParentNode<T>, query_point: P) -> Self {
+        FarthestNeighborDistance2Iterator {
+            search: BestFirstSearch::new(root, FarthestNeighborFunction { query_point }),
+        }
+    }
+}
+
+impl<'a, T, P> Iterator for FarthestNeighborDistance2Iterator<'a, T>
+where
+    P: Point,
+    T: PointDistance + RTreeObject<Envelope = AABB<P>>,
+{
+    type Item = (&'a T, Distance<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.search
+            .next()
+            .map(|(t, Reverse(distance))| (t, distance))
+    }
+}
+
+/// Iterator returned by [`RTree::farthest_neighbor_iter_with_distance_2`].
+pub struct FarthestNeighborDistance2Iterator<'a, T>
+where
+    T: PointDistance + 'a,
+{
+    search: BestFirstSearch<'a, T, FarthestNeighborFunction<T>, Reverse<Distance<T>>>,
+}
+
+impl<'a, T, P> FarthestNeighborIterator<'a, T>
+where
+    P: Point,
+    T: PointDistance + RTreeObject<Envelope = AABB<P>>,
+{
+    pub(crate) fn new(root: &'a ParentNode<T>, query_point: P) -> Self {
+        FarthestNeighborIterator {
+            iter: FarthestNeighborDistance2Iterator::new(root, query_point),
+        }
+    }
+}
+
+impl<'a, T, P> Iterator for FarthestNeighborIterator<'a, T>
+where
+    P: Point,
+    T: PointDistance + RTreeObject<Envelope = AABB<P>>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(t, _distance)| t)
+    }
+}
+
+/// Iterator returned by [`RTree::farthest_neighbor_iter`].
+pub struct FarthestNeighborIterator<'a, T>
+where
+    T: PointDistance + 'a,
+{
+    iter: FarthestNeighborDistance2Iterator<'a, T>,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::object::PointDistance;
+    use crate::primitives::Line;
+    use crate::rtree::RTree;
+    use crate::test_utilities::*;
+
+    #[test]
+    fn test_farthest_neighbor_empty() {
+        let tree: RTree<[f32; 2]> = RTree::new();
+        assert!(tree.farthest_neighbor([0.0, 213.0]).is_none());
+        assert!(tree.farthest_neighbor_iter([0.0, 213.0]).next().is_none());
+    }
+
+    #[test]
+    fn test_farthest_neighbor() {
+        let points = create_random_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+
+        let sample_points = create_random_points(100, SEED_2);
+        for sample_point in sample_points {
+            let farthest = points.iter().max_by(|l, r| {
+                l.distance_2(&sample_point)
+                    .partial_cmp(&r.distance_2(&sample_point))
+                    .unwrap()
+            });
+            assert_eq!(farthest, tree.farthest_neighbor(sample_point));
+        }
+    }
+
+    #[test]
+    fn test_farthest_neighbor_iterator() {
+        let mut points = create_random_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+
+        let sample_points = create_random_points(50, SEED_2);
+        for sample_point in sample_points {
+            points.sort_by(|r, l| {
+                l.distance_2(&sample_point)
+                    .partial_cmp(&r.distance_2(&sample_point))
+                    .unwrap()
+            });
+            let collected: Vec<_> = tree.farthest_neighbor_iter(sample_point).cloned().collect();
+            assert_eq!(points, collected);
+        }
+    }
+
+    #[test]
+    fn test_farthest_neighbor_iterator_with_distance_2() {
+        let lines = create_random_lines(500, SEED_2);
+        let tree: RTree<Line<[f64; 2]>> = RTree::bulk_load(lines);
+
+        let sample_points = create_random_points(50, SEED_1);
+        for sample_point in sample_points {
+            let mut last_distance = f64::INFINITY;
+            let mut count = 0;
+            for (line, distance) in tree.farthest_neighbor_iter_with_distance_2(sample_point) {
+                assert_eq!(line.distance_2(&sample_point), distance);
+                assert!(last_distance >= distance);
+                last_distance = distance;
+                count += 1;
+            }
+            assert_eq!(count, tree.size());
+        }
+    }
+}
@@ -1,24 +1,55 @@
+use crate::algorithm::nearest_neighbor::NearestNeighborQuery;
 use crate::node::ParentNode;
-use crate::Envelope;
+use crate::object::Distance;
 use crate::RTreeNode;
 use crate::RTreeNode::*;
 use crate::RTreeObject;
+use crate::{Envelope, Point, AABB};
 
 #[cfg(not(test))]
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use core::mem::take;
 
 #[cfg(doc)]
 use crate::RTree;
 
+/// Decides which pairs of envelopes are visited by an [IntersectionIterator].
+pub trait EnvelopePredicate<E> {
+    /// Returns `true` if the contents of both envelopes may form a pair.
+    fn matches(&self, envelope1: &E, envelope2: &E) -> bool;
+}
+
+/// Matches all intersecting envelopes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Intersecting;
+
+impl<E: Envelope> EnvelopePredicate<E> for Intersecting {
+    fn matches(&self, envelope1: &E, envelope2: &E) -> bool {
+        envelope1.intersects(envelope2)
+    }
+}
+
+/// Matches all envelopes whose squared distance is at most a maximum value.
+#[derive(Clone, Copy, Debug)]
+pub struct WithinDistance<S> {
+    max_distance_2: S,
+}
+
+impl<P: Point> EnvelopePredicate<AABB<P>> for WithinDistance<P::Scalar> {
+    fn matches(&self, envelope1: &AABB<P>, envelope2: &AABB<P>) -> bool {
+        envelope1.distance_2_to_aabb(envelope2) <= self.max_distance_2
+    }
+}
+
 /// Iterator returned by [`RTree::intersection_candidates_with_other_tree`].
-pub struct IntersectionIterator<'a, T, U = T>
+pub struct IntersectionIterator<'a, T, U = T, M = Intersecting>
 where
     T: RTreeObject,
     U: RTreeObject,
 {
     todo_list: Vec<(&'a RTreeNode<T>, &'a RTreeNode<U>)>,
     candidates: Vec<&'a RTreeNode<U>>,
+    predicate: M,
 }
 
 impl<'a, T, U> IntersectionIterator<'a, T, U>
@@ -27,16 +58,37 @@ where
     U: RTreeObject<Envelope = T::Envelope>,
 {
     pub(crate) fn new(root1: &'a ParentNode<T>, root2: &'a ParentNode<U>) -> Self {
-        let mut intersections = IntersectionIterator {
-            todo_list: Vec::new(),
-            candidates: Vec::new(),
-        };
+        Self::new_with_predicate(root1, root2, Intersecting)
+    }
+}
+
+impl<'a, T, U, M> IntersectionIterator<'a, T, U, M>
+where
+    T: RTreeObject,
+    U: RTreeObject<Envelope = T::Envelope>,
+    M: EnvelopePredicate<T::Envelope>,
+{
+    /// Creates an iterator over all pairs of objects whose envelopes match `predicate`.
+    fn new_with_predicate(
+        root1: &'a ParentNode<T>,
+        root2: &'a ParentNode<U>,
+        predicate: M,
+    ) -> Self {
+        let mut intersections = Self::empty(predicate);
         intersections.add_intersecting_children(root1, root2);
         intersections
     }
 
+    fn empty(predicate: M) -> Self {
+        IntersectionIterator {
+            todo_list: Vec::new(),
+            candidates: Vec::new(),
+            predicate,
+        }
+    }
+
     fn push_if_intersecting(&mut self, node1: &'a RTreeNode<T>, node2: &'a RTreeNode<U>) {
-        if node1.envelope().intersects(&node2.envelope()) {
+        if self.predicate.matches(&node1.envelope(), &node2.envelope()) {
             self.todo_list.push((node1, node2));
         }
     }
@@ -46,23 +98,27 @@ where
         parent1: &'a ParentNode<T>,
         parent2: &'a ParentNode<U>,
     ) {
-        if !parent1.envelope().intersects(&parent2.envelope()) {
+        if !self
+            .predicate
+            .matches(&parent1.envelope(), &parent2.envelope())
+        {
             return;
         }
-        let children1 = parent1
-            .children()
-            .iter()
-            .filter(|c1| c1.envelope().intersects(&parent2.envelope()));
-
         let mut children2 = take(&mut self.candidates);
         children2.extend(
             parent2
                 .children()
                 .iter()
-                .filter(|c2| c2.envelope().intersects(&parent1.envelope())),
+                .filter(|c2| self.predicate.matches(&parent1.envelope(), &c2.envelope())),
         );
 
-        for child1 in children1 {
+        for child1 in parent1.children() {
+            if !self
+                .predicate
+                .matches(&child1.envelope(), &parent2.envelope())
+            {
+                continue;
+            }
             for child2 in &children2 {
                 self.push_if_intersecting(child1, child2);
             }
@@ -73,10 +129,11 @@ where
     }
 }
 
-impl<'a, T, U> Iterator for IntersectionIterator<'a, T, U>
+impl<'a, T, U, M> Iterator for IntersectionIterator<'a, T, U, M>
 where
     T: RTreeObject,
     U: RTreeObject<Envelope = T::Envelope>,
+    M: EnvelopePredicate<T::Envelope>,
 {
     type Item = (&'a T, &'a U);
 
@@ -103,10 +160,159 @@ where
     }
 }
 
+/// Iterator returned by [`RTree::within_distance_join`].
+pub struct WithinDistanceJoinIterator<'a, T, U = T>
+where
+    T: RTreeObject,
+    U: RTreeObject,
+{
+    candidates: IntersectionIterator<'a, T, U, WithinDistance<Distance<T>>>,
+    max_distance_2: Distance<T>,
+}
+
+impl<'a, T, U, P> WithinDistanceJoinIterator<'a, T, U>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>> + NearestNeighborQuery<U>,
+    U: RTreeObject<Envelope = AABB<P>>,
+{
+    pub(crate) fn new(
+        root1: &'a ParentNode<T>,
+        root2: &'a ParentNode<U>,
+        max_distance_2: Distance<T>,
+    ) -> Self {
+        WithinDistanceJoinIterator {
+            candidates: IntersectionIterator::new_with_predicate(
+                root1,
+                root2,
+                WithinDistance { max_distance_2 },
+            ),
+            max_distance_2,
+        }
+    }
+}
+
+impl<'a, T, U, P> Iterator for WithinDistanceJoinIterator<'a, T, U>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>> + NearestNeighborQuery<U>,
+    U: RTreeObject<Envelope = AABB<P>>,
+{
+    type Item = (&'a T, &'a U);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let max_distance_2 = self.max_distance_2;
+        self.candidates.find(|(t, u)| {
+            t.object_distance_2_if_less_or_equal(u, max_distance_2)
+                .is_some()
+        })
+    }
+}
+
+/// Iterator returned by [`RTree::self_intersection_candidates`].
+pub struct SelfIntersectionIterator<'a, T, M = Intersecting>
+where
+    T: RTreeObject,
+{
+    todo_list: Vec<&'a ParentNode<T>>,
+    pairs: IntersectionIterator<'a, T, T, M>,
+}
+
+impl<'a, T> SelfIntersectionIterator<'a, T>
+where
+    T: RTreeObject,
+{
+    pub(crate) fn new(root: &'a ParentNode<T>) -> Self {
+        Self::new_with_predicate(root, Intersecting)
+    }
+}
+
+impl<'a, T, M> SelfIntersectionIterator<'a, T, M>
+where
+    T: RTreeObject,
+    M: EnvelopePredicate<T::Envelope>,
+{
+    fn new_with_predicate(root: &'a ParentNode<T>, predicate: M) -> Self {
+        SelfIntersectionIterator {
+            todo_list: vec![root],
+            pairs: IntersectionIterator::empty(predicate),
+        }
+    }
+}
+
+impl<'a, T, M> Iterator for SelfIntersectionIterator<'a, T, M>
+where
+    T: RTreeObject,
+    M: EnvelopePredicate<T::Envelope>,
+{
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(pair) = self.pairs.next() {
+                return Some(pair);
+            }
+            // Pairs within the same subtree are found by visiting each parent node once and
+            // pairing up each child with its succeeding siblings only.
+            let parent = self.todo_list.pop()?;
+            let children = parent.children();
+            for (index, child1) in children.iter().enumerate() {
+                if let Parent(p) = child1 {
+                    self.todo_list.push(p);
+                }
+                for child2 in &children[index + 1..] {
+                    self.pairs.push_if_intersecting(child1, child2);
+                }
+            }
+        }
+    }
+}
+
+/// Iterator returned by [`RTree::self_within_distance_join`].
+pub struct SelfWithinDistanceJoinIterator<'a, T>
+where
+    T: RTreeObject,
+{
+    candidates: SelfIntersectionIterator<'a, T, WithinDistance<Distance<T>>>,
+    max_distance_2: Distance<T>,
+}
+
+impl<'a, T, P> SelfWithinDistanceJoinIterator<'a, T>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>> + NearestNeighborQuery<T>,
+{
+    pub(crate) fn new(root: &'a ParentNode<T>, max_distance_2: Distance<T>) -> Self {
+        SelfWithinDistanceJoinIterator {
+            candidates: SelfIntersectionIterator::new_with_predicate(
+                root,
+                WithinDistance { max_distance_2 },
+            ),
+            max_distance_2,
+        }
+    }
+}
+
+impl<'a, T, P> Iterator for SelfWithinDistanceJoinIterator<'a, T>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>> + NearestNeighborQuery<T>,
+{
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let max_distance_2 = self.max_distance_2;
+        self.candidates.find(|(t1, t2)| {
+            t1.object_distance_2_if_less_or_equal(t2, max_distance_2)
+                .is_some()
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::test_utilities::*;
-    use crate::{Envelope, RTree, RTreeObject};
+    use crate::{Envelope, PointDistance, RTree, RTreeObject};
 
     #[test]
     fn test_intersection_between_trees() {
@@ -153,4 +359,126 @@ mod test {
             tree1.size()
         );
     }
+
+    #[test]
+    fn test_within_distance_join() {
+        let points1 = create_random_points(300, SEED_1);
+        let points2 = create_random_points(500, SEED_2);
+        let max_distance_2 = 0.05 * 0.05;
+
+        let mut pairs_brute_force = Vec::new();
+        for point1 in &points1 {
+            for point2 in &points2 {
+                if point1.distance_2(point2) <= max_distance_2 {
+                    pairs_brute_force.push((point1, point2));
+                }
+            }
+        }
+
+        let tree1 = RTree::bulk_load(points1.clone());
+        let tree2 = RTree::bulk_load(points2.clone());
+        let mut pairs_from_trees = tree1
+            .within_distance_join(&tree2, max_distance_2)
+            .collect::<Vec<_>>();
+
+        assert!(!pairs_brute_force.is_empty());
+        pairs_brute_force.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        pairs_from_trees.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(pairs_brute_force, pairs_from_trees);
+    }
+
+    #[test]
+    fn test_within_distance_join_refinement() {
+        let lines = create_random_lines(100, SEED_1);
+        let points = create_random_points(200, SEED_2);
+        let max_distance_2 = 0.02 * 0.02;
+
+        let mut pairs_brute_force = Vec::new();
+        for line in &lines {
+            for point in &points {
+                if line.distance_2(point) <= max_distance_2 {
+                    pairs_brute_force.push((line, point));
+                }
+            }
+        }
+
+        let tree1 = RTree::bulk_load(lines.clone());
+        let tree2 = RTree::bulk_load(points.clone());
+        let mut pairs_from_trees = tree1
+            .within_distance_join(&tree2, max_distance_2)
+            .collect::<Vec<_>>();
+
+        pairs_brute_force.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        pairs_from_trees.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(pairs_brute_force, pairs_from_trees);
+    }
+
+    #[test]
+    fn test_within_zero_distance_join() {
+        let points = create_random_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points);
+        assert_eq!(tree.within_distance_join(&tree, 0.0).count(), tree.size());
+    }
+
+    fn unordered<'a, T: PartialOrd>(
+        pairs: impl Iterator<Item = (&'a T, &'a T)>,
+    ) -> Vec<(&'a T, &'a T)> {
+        let mut result: Vec<_> = pairs
+            .map(|(a, b)| if a <= b { (a, b) } else { (b, a) })
+            .collect();
+        result.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        result
+    }
+
+    #[test]
+    fn test_self_intersection_candidates() {
+        let rectangles = create_random_rectangles(200, SEED_1);
+        let tree = RTree::bulk_load(rectangles.clone());
+
+        let mut pairs_brute_force = Vec::new();
+        for (index, rectangle1) in rectangles.iter().enumerate() {
+            for rectangle2 in &rectangles[index + 1..] {
+                if rectangle1.envelope().intersects(&rectangle2.envelope()) {
+                    pairs_brute_force.push((rectangle1, rectangle2));
+                }
+            }
+        }
+
+        let pairs_from_tree = unordered(tree.self_intersection_candidates());
+        assert!(!pairs_from_tree.is_empty());
+        assert_eq!(unordered(pairs_brute_force.into_iter()), pairs_from_tree);
+    }
+
+    #[test]
+    fn test_self_intersection_candidates_without_self_pairs() {
+        let points = create_random_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points);
+        assert_eq!(tree.self_intersection_candidates().count(), 0);
+
+        let empty: RTree<[f64; 2]> = RTree::new();
+        assert_eq!(empty.self_intersection_candidates().count(), 0);
+
+        let duplicates = RTree::bulk_load(vec![[1.0, 2.0]; 3]);
+        assert_eq!(duplicates.self_intersection_candidates().count(), 3);
+    }
+
+    #[test]
+    fn test_self_within_distance_join() {
+        let points = create_random_points(500, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+        let max_distance_2 = 0.03 * 0.03;
+
+        let mut pairs_brute_force = Vec::new();
+        for (index, point1) in points.iter().enumerate() {
+            for point2 in &points[index + 1..] {
+                if point1.distance_2(point2) <= max_distance_2 {
+                    pairs_brute_force.push((point1, point2));
+                }
+            }
+        }
+
+        let pairs_from_tree = unordered(tree.self_within_distance_join(max_distance_2));
+        assert!(!pairs_from_tree.is_empty());
+        assert_eq!(unordered(pairs_brute_force.into_iter()), pairs_from_tree);
+    }
 }
@@ -1,6 +1,9 @@
 use crate::algorithm::selection_functions::*;
+use crate::envelope::Envelope;
 use crate::node::{ParentNode, RTreeNode};
 use crate::object::RTreeObject;
+use crate::primitives::{Moving, Temporal};
+use crate::Point;
 use core::ops::ControlFlow;
 
 #[cfg(doc)]
@@ -8,8 +11,19 @@ use crate::RTree;
 
 use smallvec::SmallVec;
 
-pub use super::intersection_iterator::IntersectionIterator;
-pub use super::nearest_neighbor::{NearestNeighborDistance2Iterator, NearestNeighborIterator};
+pub use super::best_first::BestFirstIterator;
+pub use super::farthest_neighbor::{FarthestNeighborDistance2Iterator, FarthestNeighborIterator};
+pub use super::intersection_iterator::{
+    IntersectionIterator, SelfIntersectionIterator, SelfWithinDistanceJoinIterator,
+    WithinDistanceJoinIterator,
+};
+pub use super::nearest_neighbor::{
+    LocateWithinDistanceSortedIterator, NearestNeighborDistance2Iterator, NearestNeighborIterator,
+};
+pub use super::nearest_neighbor_join::KNearestNeighborsJoinIterator;
+#[cfg(feature = "rayon")]
+pub use super::parallel::ParSelectionIterator;
+pub use super::raycast::RaycastIterator;
 pub use super::removal::{DrainIterator, IntoIter};
 
 /// Iterator returned by [`RTree::locate_all_at_point`].
@@ -29,15 +43,79 @@ pub type LocateInEnvelopeIntersecting<'a, T> =
 pub type LocateInEnvelopeIntersectingMut<'a, T> =
     SelectionIteratorMut<'a, T, SelectInEnvelopeFuncIntersecting<T>>;
 
+/// Iterator returned by [`RTree::locate_containing_envelope`].
+pub type LocateContainingEnvelope<'a, T> =
+    SelectionIterator<'a, T, SelectContainingEnvelopeFunction<T>>;
+/// Iterator returned by [`RTree::locate_containing_envelope_mut`].
+pub type LocateContainingEnvelopeMut<'a, T> =
+    SelectionIteratorMut<'a, T, SelectContainingEnvelopeFunction<T>>;
+
+/// Iterator returned by [`RTree::locate_intersecting_segment`].
+pub type LocateIntersectingSegment<'a, T> =
+    SelectionIterator<'a, T, SelectSegmentIntersectingFunction<T>>;
+/// Iterator returned by [`RTree::locate_intersecting_segment_mut`].
+pub type LocateIntersectingSegmentMut<'a, T> =
+    SelectionIteratorMut<'a, T, SelectSegmentIntersectingFunction<T>>;
+
+/// Iterator returned by [`RTree::locate_intersecting_ball`].
+pub type LocateIntersectingBall<'a, T> =
+    SelectionIterator<'a, T, SelectIntersectingBallFunction<T>>;
+/// Iterator returned by [`RTree::locate_intersecting_ball_mut`].
+pub type LocateIntersectingBallMut<'a, T> =
+    SelectionIteratorMut<'a, T, SelectIntersectingBallFunction<T>>;
+
+/// Iterator returned by [`RTree::locate_in_ball`].
+pub type LocateInBall<'a, T> = SelectionIterator<'a, T, SelectInBallFunction<T>>;
+/// Iterator returned by [`RTree::locate_in_ball_mut`].
+pub type LocateInBallMut<'a, T> = SelectionIteratorMut<'a, T, SelectInBallFunction<T>>;
+
+/// Iterator returned by [`RTree::locate_in_geographic_envelope`].
+pub type LocateInGeographicEnvelope<'a, T, P> =
+    SelectionIterator<'a, T, SelectInGeographicEnvelopeFunction<P>>;
+/// Iterator returned by [`RTree::locate_in_geographic_envelope_intersecting`].
+pub type LocateInGeographicEnvelopeIntersecting<'a, T, P> =
+    SelectionIterator<'a, T, SelectInGeographicEnvelopeFuncIntersecting<P>>;
+
+/// Iterator returned by [`RTree::locate_in_envelope_intersecting_periodic`].
+pub type LocateInEnvelopeIntersectingPeriodic<'a, T, P> =
+    SelectionIterator<'a, T, SelectPeriodicEnvelopeIntersectingFunction<P>>;
+
+/// Iterator returned by [`RTree::locate_in_envelope_at`].
+pub type LocateInEnvelopeAt<'a, T, P> =
+    SelectionIterator<'a, Temporal<T, <P as Point>::Scalar>, SelectInEnvelopeAtFunction<P>>;
+
+/// Iterator returned by [`RTree::locate_in_envelope_intersecting_at`].
+pub type LocateInEnvelopeIntersectingAt<'a, T, P> =
+    SelectionIterator<'a, Moving<T, P>, SelectMovingEnvelopeIntersectingFunction<P>>;
+
 /// Iterator returned by [`RTree::iter`].
 pub type RTreeIterator<'a, T> = SelectionIterator<'a, T, SelectAllFunc>;
 /// Iterator returned by [`RTree::iter_mut`].
 pub type RTreeIteratorMut<'a, T> = SelectionIteratorMut<'a, T, SelectAllFunc>;
 
+/// Parallel iterator returned by [`RTree::par_iter`].
+#[cfg(feature = "rayon")]
+pub type ParRTreeIterator<'a, T> = ParSelectionIterator<'a, T, SelectAllFunc>;
+/// Parallel iterator returned by [`RTree::par_locate_in_envelope`].
+#[cfg(feature = "rayon")]
+pub type ParLocateInEnvelope<'a, T> = ParSelectionIterator<'a, T, SelectInEnvelopeFunction<T>>;
+
 /// Iterator returned by [`RTree::locate_within_distance`].
 pub type LocateWithinDistanceIterator<'a, T> =
     SelectionIterator<'a, T, SelectWithinDistanceFunction<T>>;
 
+/// Iterator returned by [`RTree::locate_within_metric_distance`].
+pub type LocateWithinMetricDistanceIterator<'a, T, P, M> =
+    SelectionIterator<'a, T, SelectWithinMetricDistanceFunction<P, M>>;
+
+/// Iterator returned by [`RTree::nearest_neighbor_iter_filtered`].
+pub type NearestNeighborFilteredIterator<'a, T, F> = NearestNeighborIterator<
+    'a,
+    T,
+    <<T as RTreeObject>::Envelope as Envelope>::Point,
+    SelectByPredicateFunction<F>,
+>;
+
 /// Iterator returned by `RTree::locate_*` methods.
 pub struct SelectionIterator<'a, T, Func>
 where
@@ -45,7 +123,8 @@ where
     Func: SelectionFunction<T>,
 {
     func: Func,
-    current_nodes: SmallVec<[&'a RTreeNode<T>; 24]>,
+    /// Nodes to visit, flagged if all of their contained elements are selected
+    current_nodes: SmallVec<[(&'a RTreeNode<T>, bool); 24]>,
 }
 
 impl<'a, T, Func> SelectionIterator<'a, T, Func>
@@ -54,17 +133,18 @@ where
     Func: SelectionFunction<T>,
 {
     pub(crate) fn new(root: &'a ParentNode<T>, func: Func) -> Self {
-        let current_nodes =
-            if !root.children.is_empty() && func.should_unpack_parent(&root.envelope()) {
-                root.children.iter().collect()
-            } else {
-                SmallVec::new()
-            };
-
-        SelectionIterator {
+        let mut result = SelectionIterator {
             func,
-            current_nodes,
+            current_nodes: SmallVec::new(),
+        };
+        if !root.children.is_empty() {
+            if let Some(selected) = visit_parent(&result.func, &root.envelope, false) {
+                result
+                    .current_nodes
+                    .extend(root.children.iter().map(|child| (child, selected)));
+            }
         }
+        result
     }
 }
 
@@ -76,16 +156,17 @@ where
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
-        while let Some(next) = self.current_nodes.pop() {
+        while let Some((next, selected)) = self.current_nodes.pop() {
             match next {
                 RTreeNode::Leaf(ref t) => {
-                    if self.func.should_unpack_leaf(t) {
+                    if selected || self.func.should_unpack_leaf(t) {
                         return Some(t);
                     }
                 }
                 RTreeNode::Parent(ref data) => {
-                    if self.func.should_unpack_parent(&data.envelope) {
-                        self.current_nodes.extend(&data.children);
+                    if let Some(selected) = visit_parent(&self.func, &data.envelope, selected) {
+                        self.current_nodes
+                            .extend(data.children.iter().map(|child| (child, selected)));
                     }
                 }
             }
@@ -94,6 +175,30 @@ where
     }
 }
 
+/// Returns whether the children of a parent node need to be visited and, if so, whether all
+/// elements contained within them are selected.
+///
+/// `selected` indicates if all elements contained within the parent are already known to be
+/// selected.
+pub(crate) fn visit_parent<T, Func>(
+    func: &Func,
+    envelope: &T::Envelope,
+    selected: bool,
+) -> Option<bool>
+where
+    T: RTreeObject,
+    Func: SelectionFunction<T>,
+{
+    if selected {
+        return Some(true);
+    }
+    match func.select_parent(envelope) {
+        ParentSelection::Skip => None,
+        ParentSelection::Unpack => Some(false),
+        ParentSelection::SelectAll => Some(true),
+    }
+}
+
 /// Internal iteration variant of [`SelectionIterator`]
 pub fn select_nodes<'a, T, Func, V, B>(
     root: &'a ParentNode<T>,
@@ -126,19 +231,38 @@ where
                         (args.visitor)(t)?;
                     }
                 }
-                RTreeNode::Parent(ref data) => {
-                    if args.func.should_unpack_parent(&data.envelope()) {
-                        inner(data, args)?;
-                    }
-                }
+                RTreeNode::Parent(ref data) => match args.func.select_parent(&data.envelope()) {
+                    ParentSelection::Skip => {}
+                    ParentSelection::Unpack => inner(data, args)?,
+                    ParentSelection::SelectAll => inner_all(data, args.visitor)?,
+                },
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    fn inner_all<'a, T, V, B>(parent: &'a ParentNode<T>, visitor: &mut V) -> ControlFlow<B>
+    where
+        T: RTreeObject,
+        V: FnMut(&'a T) -> ControlFlow<B>,
+    {
+        for node in parent.children.iter() {
+            match node {
+                RTreeNode::Leaf(ref t) => visitor(t)?,
+                RTreeNode::Parent(ref data) => inner_all(data, visitor)?,
             }
         }
 
         ControlFlow::Continue(())
     }
 
-    if !root.children.is_empty() && func.should_unpack_parent(&root.envelope()) {
-        inner(root, &mut Args { func, visitor })?;
+    if !root.children.is_empty() {
+        match func.select_parent(&root.envelope()) {
+            ParentSelection::Skip => {}
+            ParentSelection::Unpack => inner(root, &mut Args { func, visitor })?,
+            ParentSelection::SelectAll => inner_all(root, visitor)?,
+        }
     }
 
     ControlFlow::Continue(())
@@ -151,7 +275,8 @@ where
     Func: SelectionFunction<T>,
 {
     func: Func,
-    current_nodes: SmallVec<[&'a mut RTreeNode<T>; 32]>,
+    /// Nodes to visit, flagged if all of their contained elements are selected
+    current_nodes: SmallVec<[(&'a mut RTreeNode<T>, bool); 32]>,
 }
 
 impl<'a, T, Func> SelectionIteratorMut<'a, T, Func>
@@ -160,17 +285,18 @@ where
     Func: SelectionFunction<T>,
 {
     pub(crate) fn new(root: &'a mut ParentNode<T>, func: Func) -> Self {
-        let current_nodes =
-            if !root.children.is_empty() && func.should_unpack_parent(&root.envelope()) {
-                root.children.iter_mut().collect()
-            } else {
-                SmallVec::new()
-            };
-
-        SelectionIteratorMut {
+        let mut result = SelectionIteratorMut {
             func,
-            current_nodes,
+            current_nodes: SmallVec::new(),
+        };
+        if !root.children.is_empty() {
+            if let Some(selected) = visit_parent(&result.func, &root.envelope, false) {
+                result
+                    .current_nodes
+                    .extend(root.children.iter_mut().map(|child| (child, selected)));
+            }
         }
+        result
     }
 }
 
@@ -182,16 +308,17 @@ where
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<&'a mut T> {
-        while let Some(next) = self.current_nodes.pop() {
+        while let Some((next, selected)) = self.current_nodes.pop() {
             match next {
                 RTreeNode::Leaf(ref mut t) => {
-                    if self.func.should_unpack_leaf(t) {
+                    if selected || self.func.should_unpack_leaf(t) {
                         return Some(t);
                     }
                 }
                 RTreeNode::Parent(ref mut data) => {
-                    if self.func.should_unpack_parent(&data.envelope) {
-                        self.current_nodes.extend(&mut data.children);
+                    if let Some(selected) = visit_parent(&self.func, &data.envelope, selected) {
+                        self.current_nodes
+                            .extend(data.children.iter_mut().map(|child| (child, selected)));
                     }
                 }
             }
@@ -200,6 +327,41 @@ where
     }
 }
 
+/// Returns `true` if any element is selected by `func`.
+///
+/// Stops at the first selected element without allocating. Any non-empty node for
+/// which [`SelectionFunction::select_parent`] returns [`ParentSelection::SelectAll`] is
+/// known to contain a selected element and is not descended into.
+pub fn any_selected<T, Func>(root: &ParentNode<T>, func: &Func) -> bool
+where
+    T: RTreeObject,
+    Func: SelectionFunction<T>,
+{
+    fn inner<T, Func>(parent: &ParentNode<T>, func: &Func) -> bool
+    where
+        T: RTreeObject,
+        Func: SelectionFunction<T>,
+    {
+        parent.children.iter().any(|node| match node {
+            RTreeNode::Leaf(ref t) => func.should_unpack_leaf(t),
+            RTreeNode::Parent(ref data) => match func.select_parent(&data.envelope) {
+                ParentSelection::Skip => false,
+                ParentSelection::Unpack => inner(data, func),
+                ParentSelection::SelectAll => true,
+            },
+        })
+    }
+
+    if root.children.is_empty() {
+        return false;
+    }
+    match func.select_parent(&root.envelope) {
+        ParentSelection::Skip => false,
+        ParentSelection::Unpack => inner(root, func),
+        ParentSelection::SelectAll => true,
+    }
+}
+
 /// Internal iteration variant of [`SelectionIteratorMut`]
 pub fn select_nodes_mut<'a, T, Func, V, B>(
     root: &'a mut ParentNode<T>,
@@ -233,8 +395,10 @@ where
                     }
                 }
                 RTreeNode::Parent(ref mut data) => {
-                    if args.func.should_unpack_parent(&data.envelope()) {
-                        inner(data, args)?;
+                    match args.func.select_parent(&data.envelope()) {
+                        ParentSelection::Skip => {}
+                        ParentSelection::Unpack => inner(data, args)?,
+                        ParentSelection::SelectAll => inner_all(data, args.visitor)?,
                     }
                 }
             }
@@ -243,8 +407,27 @@ where
         ControlFlow::Continue(())
     }
 
-    if !root.children.is_empty() && func.should_unpack_parent(&root.envelope()) {
-        inner(root, &mut Args { func, visitor })?;
+    fn inner_all<'a, T, V, B>(parent: &'a mut ParentNode<T>, visitor: &mut V) -> ControlFlow<B>
+    where
+        T: RTreeObject,
+        V: FnMut(&'a mut T) -> ControlFlow<B>,
+    {
+        for node in parent.children.iter_mut() {
+            match node {
+                RTreeNode::Leaf(ref mut t) => visitor(t)?,
+                RTreeNode::Parent(ref mut data) => inner_all(data, visitor)?,
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    if !root.children.is_empty() {
+        match func.select_parent(&root.envelope()) {
+            ParentSelection::Skip => {}
+            ParentSelection::Unpack => inner(root, &mut Args { func, visitor })?,
+            ParentSelection::SelectAll => inner_all(root, visitor)?,
+        }
     }
 
     ControlFlow::Continue(())
@@ -253,11 +436,12 @@ where
 #[cfg(test)]
 mod test {
     use crate::aabb::AABB;
+    use crate::algorithm::selection_functions::SelectInEnvelopeFuncIntersecting;
     use crate::envelope::Envelope;
-    use crate::object::RTreeObject;
+    use crate::object::{PointDistance, RTreeObject};
     use crate::rtree::RTree;
     use crate::test_utilities::{create_random_points, create_random_rectangles, SEED_1};
-    use crate::SelectionFunction;
+    use crate::{ParentSelection, SelectionFunction};
 
     #[test]
     fn test_root_node_is_not_always_unpacked() {
@@ -324,6 +508,65 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_locate_containing_envelope() {
+        let rectangles = create_random_rectangles(200, SEED_1);
+        let mut tree = RTree::bulk_load(rectangles.clone());
+        for rectangle in rectangles.iter().take(20) {
+            let envelope = AABB::from_point(rectangle.envelope().center());
+            let expected: Vec<_> = rectangles
+                .iter()
+                .filter(|r| r.envelope().contains_envelope(&envelope))
+                .collect();
+            let located: Vec<_> = tree.locate_containing_envelope(envelope).collect();
+            assert!(!expected.is_empty());
+            assert_eq!(located.len(), expected.len());
+            for rectangle in &expected {
+                assert!(located.contains(rectangle));
+            }
+            assert_eq!(
+                tree.locate_containing_envelope_mut(envelope).count(),
+                expected.len()
+            );
+        }
+
+        let envelope = AABB::from_point(rectangles[0].envelope().center());
+        let expected = tree.locate_containing_envelope(envelope).count();
+        assert_eq!(tree.drain_containing_envelope(envelope).count(), expected);
+        assert_eq!(tree.locate_containing_envelope(envelope).count(), 0);
+        assert_eq!(tree.size(), rectangles.len() - expected);
+    }
+
+    #[test]
+    fn test_any_queries() {
+        let points = create_random_points(500, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+        for envelope in create_random_rectangles(50, SEED_1) {
+            let envelope = envelope.envelope();
+            assert_eq!(
+                tree.any_in_envelope(envelope),
+                points.iter().any(|p| envelope.contains_point(p))
+            );
+            assert_eq!(
+                tree.any_intersecting(envelope),
+                points.iter().any(|p| envelope.intersects(&p.envelope()))
+            );
+            let center = envelope.center();
+            assert_eq!(
+                tree.any_within_distance(center, 1e-4),
+                points.iter().any(|p| p.distance_2(&center) <= 1e-4)
+            );
+        }
+        let everything = AABB::from_corners([-1.0, -1.0], [2.0, 2.0]);
+        assert!(tree.any_in_envelope(everything));
+        assert!(tree.any_with_selection_function(SelectInEnvelopeFuncIntersecting::new(everything)));
+        assert!(!tree.any_in_envelope(AABB::from_corners([3.0, 3.0], [4.0, 4.0])));
+
+        let empty: RTree<[f64; 2]> = RTree::new();
+        assert!(!empty.any_in_envelope(everything));
+        assert!(!empty.any_intersecting(everything));
+    }
+
     #[test]
     fn test_locate_with_selection_func() {
         use crate::SelectionFunction;
@@ -359,6 +602,321 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_locate_intersecting_segment() {
+        use crate::primitives::Line;
+
+        let rectangles = create_random_rectangles(200, SEED_1);
+        let mut tree = RTree::bulk_load(rectangles.clone());
+        let segment = Line::new([0.1, 0.9], [0.8, 0.05]);
+
+        let intersecting: Vec<_> = rectangles
+            .iter()
+            .filter(|rectangle| segment.intersects_aabb(&rectangle.envelope()))
+            .cloned()
+            .collect();
+        let len = intersecting.len();
+        let candidates = tree
+            .locate_in_envelope_intersecting(segment.envelope())
+            .count();
+        assert!(0 < len && len < candidates);
+
+        let located: Vec<_> = tree.locate_intersecting_segment(segment).cloned().collect();
+        assert_eq!(len, located.len());
+        for rectangle in &intersecting {
+            assert!(located.contains(rectangle));
+        }
+        assert_eq!(tree.locate_intersecting_segment_mut(segment).count(), len);
+    }
+
+    #[test]
+    fn test_locate_ball() {
+        let rectangles = create_random_rectangles(300, SEED_1);
+        let mut tree = RTree::bulk_load(rectangles.clone());
+        let center = [0.4, 0.6];
+        let squared_radius = 0.3 * 0.3;
+
+        let intersecting: Vec<_> = rectangles
+            .iter()
+            .filter(|rectangle| rectangle.envelope().distance_2(&center) <= squared_radius)
+            .cloned()
+            .collect();
+        let contained: Vec<_> = intersecting
+            .iter()
+            .filter(|rectangle| rectangle.envelope().max_distance_2(&center) <= squared_radius)
+            .cloned()
+            .collect();
+        assert!(!contained.is_empty() && contained.len() < intersecting.len());
+
+        let located: Vec<_> = tree
+            .locate_intersecting_ball(center, squared_radius)
+            .cloned()
+            .collect();
+        assert_eq!(located.len(), intersecting.len());
+        for rectangle in &intersecting {
+            assert!(located.contains(rectangle));
+        }
+        assert_eq!(
+            tree.locate_intersecting_ball_mut(center, squared_radius)
+                .count(),
+            intersecting.len()
+        );
+
+        let located: Vec<_> = tree
+            .locate_in_ball(center, squared_radius)
+            .cloned()
+            .collect();
+        assert_eq!(located.len(), contained.len());
+        for rectangle in &contained {
+            assert!(located.contains(rectangle));
+        }
+        assert_eq!(
+            tree.locate_in_ball_mut(center, squared_radius).count(),
+            contained.len()
+        );
+    }
+
+    #[test]
+    fn test_locate_in_geographic_envelope() {
+        let points: Vec<_> = create_random_points(500, SEED_1)
+            .into_iter()
+            .map(|[x, y]| [x * 360.0 - 180.0, y * 180.0 - 90.0])
+            .collect();
+        let tree = RTree::bulk_load(points.clone());
+
+        let contains = |[lon, lat]: [f64; 2]| (lon >= 150.0 || lon <= -160.0) && lat.abs() <= 30.0;
+        let mut expected: Vec<_> = points.iter().cloned().filter(|p| contains(*p)).collect();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!(!expected.is_empty());
+
+        let mut located: Vec<_> = tree
+            .locate_in_geographic_envelope([150.0, -30.0], [-160.0, 30.0])
+            .cloned()
+            .collect();
+        located.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(expected, located);
+
+        let mut located: Vec<_> = tree
+            .locate_in_geographic_envelope_intersecting([150.0, -30.0], [-160.0, 30.0])
+            .cloned()
+            .collect();
+        located.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(expected, located);
+
+        // Boxes not crossing the antimeridian behave like regular envelopes
+        let envelope = AABB::from_corners([-160.0, -30.0], [150.0, 30.0]);
+        assert_eq!(
+            tree.locate_in_geographic_envelope([-160.0, -30.0], [150.0, 30.0])
+                .count(),
+            tree.locate_in_envelope(envelope).count()
+        );
+    }
+
+    #[test]
+    fn test_locate_in_geographic_envelope_intersecting_spanning_objects() {
+        use crate::primitives::Rectangle;
+
+        let tree = RTree::bulk_load(vec![
+            Rectangle::from_corners([-180.0, 0.0], [180.0, 1.0]),
+            Rectangle::from_corners([0.0, 0.0], [10.0, 1.0]),
+        ]);
+        let located: Vec<_> = tree
+            .locate_in_geographic_envelope_intersecting([170.0, -5.0], [-170.0, 5.0])
+            .collect();
+        assert_eq!(located.len(), 1);
+        assert_eq!(
+            tree.locate_in_geographic_envelope([170.0, -5.0], [-170.0, 5.0])
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_locate_in_envelope_intersecting_periodic() {
+        let extent = [1.0, 1.0];
+        let rectangles = create_random_rectangles(300, SEED_1);
+        let tree = RTree::bulk_load(rectangles.clone());
+
+        for (lower, upper) in [
+            ([-0.1, -0.1], [0.1, 0.1]),
+            ([0.8, 0.3], [1.2, 0.5]),
+            ([0.4, 0.4], [0.6, 0.6]),
+            ([-3.0, 0.2], [-2.9, 0.3]),
+            ([0.0, 0.9], [2.0, 1.05]),
+        ] {
+            // Check all shifted copies of the query envelope by hand
+            let mut expected: Vec<_> = rectangles
+                .iter()
+                .filter(|rectangle| {
+                    (-4..=4).any(|dx| {
+                        (-4..=4).any(|dy| {
+                            let shift = |[x, y]: [f64; 2]| [x + dx as f64, y + dy as f64];
+                            AABB::from_corners(shift(lower), shift(upper))
+                                .intersects(&rectangle.envelope())
+                        })
+                    })
+                })
+                .cloned()
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let envelope = AABB::from_corners(lower, upper);
+            let mut located: Vec<_> = tree
+                .locate_in_envelope_intersecting_periodic(envelope, extent)
+                .cloned()
+                .collect();
+            located.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(expected, located);
+        }
+    }
+
+    #[test]
+    fn test_locate_in_polytope() {
+        use crate::primitives::HalfSpace;
+        use crate::SelectInPolytopeFunction;
+        use core::ops::ControlFlow;
+
+        let rectangles = create_random_rectangles(300, SEED_1);
+        let mut tree = RTree::bulk_load(rectangles.clone());
+        let polytope = SelectInPolytopeFunction::new(vec![
+            HalfSpace::new([-1.0, 0.0], -0.2),
+            HalfSpace::new([0.0, -1.0], -0.1),
+            HalfSpace::new([1.0, 1.0], 1.2),
+        ]);
+
+        let mut expected: Vec<_> = rectangles
+            .iter()
+            .filter(|rectangle| {
+                polytope
+                    .half_spaces()
+                    .iter()
+                    .all(|half_space| half_space.intersects_aabb(&rectangle.envelope()))
+            })
+            .cloned()
+            .collect();
+        assert!(10 < expected.len() && expected.len() < 290);
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut located: Vec<_> = tree
+            .locate_with_selection_function(polytope.clone())
+            .cloned()
+            .collect();
+        located.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(expected, located);
+
+        let mut located = Vec::new();
+        let _ = super::select_nodes(tree.root(), &polytope, &mut |r| {
+            located.push(*r);
+            ControlFlow::<()>::Continue(())
+        });
+        located.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(expected, located);
+
+        let mut located: Vec<_> = tree
+            .locate_with_selection_function_mut(polytope)
+            .map(|r| *r)
+            .collect();
+        located.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(expected, located);
+    }
+
+    #[test]
+    fn test_locate_in_polygon() {
+        use crate::SelectInPolygonFunction;
+
+        // A concave, star shaped lasso
+        let lasso = SelectInPolygonFunction::new(vec![
+            [0.5, 0.05],
+            [0.6, 0.4],
+            [0.95, 0.5],
+            [0.6, 0.6],
+            [0.5, 0.95],
+            [0.4, 0.6],
+            [0.05, 0.5],
+            [0.4, 0.4],
+        ]);
+
+        let points = create_random_points(1000, SEED_1);
+        let mut tree = RTree::bulk_load(points.clone());
+        let mut expected: Vec<_> = points
+            .iter()
+            .filter(|point| lasso.contains_point(point))
+            .cloned()
+            .collect();
+        assert!(20 < expected.len() && expected.len() < 500);
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut located: Vec<_> = tree
+            .locate_with_selection_function(lasso.clone())
+            .cloned()
+            .collect();
+        located.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(expected, located);
+
+        let mut located: Vec<_> = tree
+            .locate_with_selection_function_mut(lasso.clone())
+            .map(|point| *point)
+            .collect();
+        located.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(expected, located);
+
+        let rectangles = create_random_rectangles(300, SEED_1);
+        let tree = RTree::bulk_load(rectangles.clone());
+        let expected = rectangles
+            .iter()
+            .filter(|rectangle| lasso.contains_point(&rectangle.envelope().center()))
+            .count();
+        assert_eq!(tree.locate_with_selection_function(lasso).count(), expected);
+    }
+
+    #[test]
+    fn test_select_all_skips_checks() {
+        use core::cell::Cell;
+        use core::ops::ControlFlow;
+
+        struct SelectAllCounting {
+            checks: Cell<usize>,
+        }
+
+        impl SelectionFunction<[f64; 2]> for &SelectAllCounting {
+            fn should_unpack_parent(&self, _: &AABB<[f64; 2]>) -> bool {
+                self.checks.set(self.checks.get() + 1);
+                true
+            }
+
+            fn should_unpack_leaf(&self, _: &[f64; 2]) -> bool {
+                self.checks.set(self.checks.get() + 1);
+                true
+            }
+
+            fn select_parent(&self, envelope: &AABB<[f64; 2]>) -> ParentSelection {
+                if envelope.upper()[0] <= 2.0 {
+                    ParentSelection::SelectAll
+                } else {
+                    ParentSelection::Unpack
+                }
+            }
+        }
+
+        let mut tree = RTree::bulk_load(create_random_points(500, SEED_1));
+        let func = SelectAllCounting {
+            checks: Cell::new(0),
+        };
+        assert_eq!(tree.locate_with_selection_function(&func).count(), 500);
+        assert_eq!(tree.locate_with_selection_function_mut(&func).count(), 500);
+        let mut count = 0;
+        let _ = super::select_nodes(tree.root(), &&func, &mut |_| {
+            count += 1;
+            ControlFlow::<()>::Continue(())
+        });
+        let _ = super::select_nodes_mut(tree.root_mut(), &&func, &mut |_| {
+            count += 1;
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(count, 1000);
+        assert_eq!(func.checks.get(), 0);
+    }
+
     #[test]
     fn test_iteration() {
         const NUM_POINTS: usize = 1000;
@@ -1,8 +1,17 @@
+pub mod batch;
+pub mod best_first;
 pub mod bulk_load;
+pub mod closest_pairs;
+pub mod farthest_neighbor;
 pub mod intersection_iterator;
 /// Iterator types
 pub mod iterators;
 pub mod nearest_neighbor;
+pub mod nearest_neighbor_join;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod raycast;
 pub mod removal;
+pub mod reverse_nearest_neighbor;
 pub mod rstar;
 pub mod selection_functions;
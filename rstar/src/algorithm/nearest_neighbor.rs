@@ -1,9 +1,11 @@
+use crate::algorithm::best_first::{BestFirstSearch, PrunedBestFirstFunction};
+use crate::algorithm::selection_functions::{SelectAllFunc, SelectionFunction};
 use crate::point::min_inline;
 use crate::{
     node::{ParentNode, RTreeNode},
     object::Distance,
 };
-use crate::{Envelope, PointDistance, RTreeObject};
+use crate::{Envelope, Point, PointDistance, RTreeObject};
 
 #[cfg(doc)]
 use crate::RTree;
@@ -15,132 +17,275 @@ use core::mem::replace;
 use heapless::binary_heap as static_heap;
 use num_traits::Bounded;
 
-struct RTreeNodeDistanceWrapper<'a, T>
+/// Defines query geometries that can be used for nearest neighbor searches.
+///
+/// Nearest neighbor queries like [RTree::nearest_neighbor] look for the objects
+/// closest to a query _point_. Implementing this trait allows to use other
+/// geometries as query, e.g. to find the line closest to a given rectangle.
+/// Such a query is issued by calling [RTree::nearest_neighbor_to] or
+/// [RTree::nearest_neighbor_iter_to].
+///
+/// The search is guided by a lower bound of the distance between the query and
+/// all objects contained in an envelope. A tighter bound will result in fewer
+/// visited nodes.
+///
+/// This trait is implemented for any [Point](crate::Point) and [PointDistance]
+/// object. [AABB](crate::AABB), [Line](crate::primitives::Line) and
+/// [Rectangle](crate::primitives::Rectangle) queries can be used with any object
+/// implementing [GeometryDistance](crate::GeometryDistance) for their geometry.
+///
+/// # Notes
+/// - [envelope_distance_2](Self::envelope_distance_2) **must** never be larger than
+///   the [object_distance_2](Self::object_distance_2) of any object contained within
+///   the envelope. Otherwise, the search may skip the nearest neighbor.
+/// - As with [PointDistance::distance_2], any distance metric may be used as long
+///   as the two methods are consistent with each other.
+///
+/// # Example
+/// ```
+/// use rstar::RTree;
+/// use rstar::primitives::{Line, Rectangle};
+///
+/// let roads = RTree::bulk_load(vec![
+///     Line::new([0.0, 0.0], [10.0, 0.0]),
+///     Line::new([0.0, 5.0], [10.0, 5.0]),
+/// ]);
+///
+/// let building = Rectangle::from_corners([4.0, 3.0], [6.0, 4.0]);
+/// let (road, distance_2) = roads.nearest_neighbor_to_with_distance_2(building).unwrap();
+/// assert_eq!(road, &Line::new([0.0, 5.0], [10.0, 5.0]));
+/// assert_eq!(distance_2, 1.0);
+/// ```
+pub trait NearestNeighborQuery<T>
 where
-    T: PointDistance + 'a,
+    T: RTreeObject,
 {
-    node: &'a RTreeNode<T>,
-    distance: Distance<T>,
+    /// Returns a lower bound of the squared distance between this query and any
+    /// object contained within `envelope`.
+    fn envelope_distance_2(&self, envelope: &T::Envelope) -> Distance<T>;
+
+    /// Returns the squared distance between this query and an object.
+    fn object_distance_2(&self, object: &T) -> Distance<T>;
+
+    /// Returns the squared distance between this query and an object, or `None`
+    /// if the distance is larger than a given maximum value.
+    ///
+    /// The provided default implementation will use the distance to the object's
+    /// envelope as a lower bound.
+    fn object_distance_2_if_less_or_equal(
+        &self,
+        object: &T,
+        max_distance_2: Distance<T>,
+    ) -> Option<Distance<T>> {
+        if self.envelope_distance_2(&object.envelope()) <= max_distance_2 {
+            let distance_2 = self.object_distance_2(object);
+            if distance_2 <= max_distance_2 {
+                return Some(distance_2);
+            }
+        }
+        None
+    }
+
+    /// Returns an upper bound of the squared distance between this query and the
+    /// object closest to it within `envelope`.
+    ///
+    /// This helps to prune the search for a single nearest neighbor. The provided
+    /// default implementation returns the maximum value, meaning that no pruning
+    /// takes place.
+    ///
+    /// See also: [Envelope::min_max_dist_2]
+    fn envelope_min_max_distance_2(&self, _envelope: &T::Envelope) -> Distance<T> {
+        Bounded::max_value()
+    }
 }
 
-impl<T> PartialEq for RTreeNodeDistanceWrapper<'_, T>
+impl<P, T> NearestNeighborQuery<T> for P
 where
+    P: Point,
     T: PointDistance,
+    T::Envelope: Envelope<Point = P>,
 {
+    fn envelope_distance_2(&self, envelope: &T::Envelope) -> Distance<T> {
+        envelope.distance_2(self)
+    }
+
+    fn object_distance_2(&self, object: &T) -> Distance<T> {
+        object.distance_2(self)
+    }
+
+    fn object_distance_2_if_less_or_equal(
+        &self,
+        object: &T,
+        max_distance_2: Distance<T>,
+    ) -> Option<Distance<T>> {
+        object.distance_2_if_less_or_equal(self, max_distance_2)
+    }
+
+    fn envelope_min_max_distance_2(&self, envelope: &T::Envelope) -> Distance<T> {
+        envelope.min_max_dist_2(self)
+    }
+}
+
+/// A heap entry ordering nodes, or pairs of nodes, by ascending distance.
+pub(crate) struct NodeDistanceWrapper<N, D> {
+    pub(crate) node: N,
+    pub(crate) distance: D,
+}
+
+/// A heap entry ordering the nodes of a tree by ascending distance.
+///
+/// The distance defaults to the tree's [Distance] type but can be any ordered key.
+pub(crate) type RTreeNodeDistanceWrapper<'a, T, D = Distance<T>> =
+    NodeDistanceWrapper<&'a RTreeNode<T>, D>;
+
+impl<N, D: PartialOrd> PartialEq for NodeDistanceWrapper<N, D> {
     fn eq(&self, other: &Self) -> bool {
         self.distance == other.distance
     }
 }
 
-impl<T> PartialOrd for RTreeNodeDistanceWrapper<'_, T>
-where
-    T: PointDistance,
-{
+impl<N, D: PartialOrd> PartialOrd for NodeDistanceWrapper<N, D> {
     fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl<T> Eq for RTreeNodeDistanceWrapper<'_, T> where T: PointDistance {}
+impl<N, D: PartialOrd> Eq for NodeDistanceWrapper<N, D> {}
 
-impl<T> Ord for RTreeNodeDistanceWrapper<'_, T>
-where
-    T: PointDistance,
-{
+impl<N, D: PartialOrd> Ord for NodeDistanceWrapper<N, D> {
     fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
         // Inverse comparison creates a min heap
         other.distance.partial_cmp(&self.distance).unwrap()
     }
 }
 
-impl<'a, T> NearestNeighborDistance2Iterator<'a, T>
+/// Orders objects by their distance to a query, skipping nodes and objects rejected by a
+/// [`SelectionFunction`].
+pub(crate) struct NearestNeighborFunction<Q, S> {
+    query: Q,
+    selection_function: S,
+}
+
+impl<T, Q, S> PrunedBestFirstFunction<T> for NearestNeighborFunction<Q, S>
 where
-    T: PointDistance,
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
+    S: SelectionFunction<T>,
 {
-    pub(crate) fn new(
+    type Key = Distance<T>;
+
+    fn node_key(&self, envelope: &T::Envelope) -> Option<Distance<T>> {
+        if self.selection_function.should_unpack_parent(envelope) {
+            Some(self.query.envelope_distance_2(envelope))
+        } else {
+            None
+        }
+    }
+
+    fn object_key(&self, object: &T) -> Option<Distance<T>> {
+        if self.selection_function.should_unpack_leaf(object) {
+            Some(self.query.object_distance_2(object))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T, Q> NearestNeighborDistance2Iterator<'a, T, Q>
+where
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
+{
+    pub(crate) fn new(root: &'a ParentNode<T>, query: Q) -> Self {
+        Self::new_with_selection_function(root, query, SelectAllFunc)
+    }
+}
+
+impl<'a, T, Q, S> NearestNeighborDistance2Iterator<'a, T, Q, S>
+where
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
+    S: SelectionFunction<T>,
+{
+    pub(crate) fn new_with_selection_function(
         root: &'a ParentNode<T>,
-        query_point: <T::Envelope as Envelope>::Point,
+        query: Q,
+        selection_function: S,
     ) -> Self {
-        let mut result = NearestNeighborDistance2Iterator {
-            nodes: SmallHeap::new(),
-            query_point,
+        let function = NearestNeighborFunction {
+            query,
+            selection_function,
         };
-        result.extend_heap(&root.children);
-        result
-    }
-
-    fn extend_heap(&mut self, children: &'a [RTreeNode<T>]) {
-        let &mut NearestNeighborDistance2Iterator {
-            ref mut nodes,
-            ref query_point,
-        } = self;
-        nodes.extend(children.iter().map(|child: &RTreeNode<T>| {
-            let distance = match child {
-                RTreeNode::Parent(ref data) => data.envelope.distance_2(query_point),
-                RTreeNode::Leaf(ref t) => t.distance_2(query_point),
-            };
-
-            RTreeNodeDistanceWrapper {
-                node: child,
-                distance,
-            }
-        }));
+        NearestNeighborDistance2Iterator {
+            search: BestFirstSearch::new(root, function),
+        }
     }
 }
 
-impl<'a, T> Iterator for NearestNeighborDistance2Iterator<'a, T>
+impl<'a, T, Q, S> Iterator for NearestNeighborDistance2Iterator<'a, T, Q, S>
 where
-    T: PointDistance,
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
+    S: SelectionFunction<T>,
 {
     type Item = (&'a T, Distance<T>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(current) = self.nodes.pop() {
-            match current {
-                RTreeNodeDistanceWrapper {
-                    node: RTreeNode::Parent(ref data),
-                    ..
-                } => {
-                    self.extend_heap(&data.children);
-                }
-                RTreeNodeDistanceWrapper {
-                    node: RTreeNode::Leaf(ref t),
-                    distance,
-                } => {
-                    return Some((t, distance));
-                }
-            }
-        }
-        None
+        self.search.next()
     }
 }
 
-/// Iterator returned by [`RTree::nearest_neighbor_iter_with_distance_2`].
-pub struct NearestNeighborDistance2Iterator<'a, T>
+/// Iterator returned by [`RTree::nearest_neighbor_iter_with_distance_2`] and
+/// [`RTree::nearest_neighbor_iter_to_with_distance_2`].
+///
+/// Only objects selected by the [`SelectionFunction`] `S` are returned.
+pub struct NearestNeighborDistance2Iterator<
+    'a,
+    T,
+    Q = <<T as RTreeObject>::Envelope as Envelope>::Point,
+    S = SelectAllFunc,
+> where
+    T: RTreeObject + 'a,
+{
+    search: BestFirstSearch<'a, T, NearestNeighborFunction<Q, S>, Distance<T>>,
+}
+
+impl<'a, T, Q> NearestNeighborIterator<'a, T, Q>
 where
-    T: PointDistance + 'a,
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
 {
-    nodes: SmallHeap<RTreeNodeDistanceWrapper<'a, T>>,
-    query_point: <T::Envelope as Envelope>::Point,
+    pub(crate) fn new(root: &'a ParentNode<T>, query: Q) -> Self {
+        Self::new_with_selection_function(root, query, SelectAllFunc)
+    }
 }
 
-impl<'a, T> NearestNeighborIterator<'a, T>
+impl<'a, T, Q, S> NearestNeighborIterator<'a, T, Q, S>
 where
-    T: PointDistance,
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
+    S: SelectionFunction<T>,
 {
-    pub(crate) fn new(
+    pub(crate) fn new_with_selection_function(
         root: &'a ParentNode<T>,
-        query_point: <T::Envelope as Envelope>::Point,
+        query: Q,
+        selection_function: S,
     ) -> Self {
         NearestNeighborIterator {
-            iter: NearestNeighborDistance2Iterator::new(root, query_point),
+            iter: NearestNeighborDistance2Iterator::new_with_selection_function(
+                root,
+                query,
+                selection_function,
+            ),
         }
     }
 }
 
-impl<'a, T> Iterator for NearestNeighborIterator<'a, T>
+impl<'a, T, Q, S> Iterator for NearestNeighborIterator<'a, T, Q, S>
 where
-    T: PointDistance,
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
+    S: SelectionFunction<T>,
 {
     type Item = &'a T;
 
@@ -149,15 +294,93 @@ where
     }
 }
 
-/// Iterator returned by [`RTree::nearest_neighbor_iter`].
-pub struct NearestNeighborIterator<'a, T>
+/// Iterator returned by [`RTree::nearest_neighbor_iter`] and
+/// [`RTree::nearest_neighbor_iter_to`].
+///
+/// Only objects selected by the [`SelectionFunction`] `S` are returned.
+pub struct NearestNeighborIterator<
+    'a,
+    T,
+    Q = <<T as RTreeObject>::Envelope as Envelope>::Point,
+    S = SelectAllFunc,
+> where
+    T: RTreeObject + 'a,
+{
+    iter: NearestNeighborDistance2Iterator<'a, T, Q, S>,
+}
+
+/// Orders objects by their distance to a query point, pruning all nodes and objects
+/// farther away than a maximum distance.
+pub(crate) struct WithinDistanceFunction<T>
+where
+    T: RTreeObject,
+{
+    query_point: <T::Envelope as Envelope>::Point,
+    max_distance_2: Distance<T>,
+}
+
+impl<T> PrunedBestFirstFunction<T> for WithinDistanceFunction<T>
+where
+    T: PointDistance,
+{
+    type Key = Distance<T>;
+
+    fn node_key(&self, envelope: &T::Envelope) -> Option<Distance<T>> {
+        let distance_2 = envelope.distance_2(&self.query_point);
+        if distance_2 <= self.max_distance_2 {
+            Some(distance_2)
+        } else {
+            None
+        }
+    }
+
+    fn object_key(&self, object: &T) -> Option<Distance<T>> {
+        object.distance_2_if_less_or_equal(&self.query_point, self.max_distance_2)
+    }
+}
+
+/// Iterator returned by [`RTree::locate_within_distance_sorted`].
+///
+/// Yields `(object, distance^2)` tuples in ascending order of their distance. Nodes
+/// farther away than the maximum distance are never pushed onto the heap.
+pub struct LocateWithinDistanceSortedIterator<'a, T>
 where
     T: PointDistance + 'a,
 {
-    iter: NearestNeighborDistance2Iterator<'a, T>,
+    search: BestFirstSearch<'a, T, WithinDistanceFunction<T>, Distance<T>>,
 }
 
-enum SmallHeap<T: Ord> {
+impl<'a, T> LocateWithinDistanceSortedIterator<'a, T>
+where
+    T: PointDistance,
+{
+    pub(crate) fn new(
+        root: &'a ParentNode<T>,
+        query_point: <T::Envelope as Envelope>::Point,
+        max_distance_2: Distance<T>,
+    ) -> Self {
+        let function = WithinDistanceFunction {
+            query_point,
+            max_distance_2,
+        };
+        LocateWithinDistanceSortedIterator {
+            search: BestFirstSearch::new(root, function),
+        }
+    }
+}
+
+impl<'a, T> Iterator for LocateWithinDistanceSortedIterator<'a, T>
+where
+    T: PointDistance,
+{
+    type Item = (&'a T, Distance<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.search.next()
+    }
+}
+
+pub(crate) enum SmallHeap<T: Ord> {
     Stack(static_heap::BinaryHeap<T, static_heap::Max, 32>),
     Heap(BinaryHeap<T>),
 }
@@ -189,18 +412,20 @@ impl<T: Ord> SmallHeap<T> {
 
     pub fn extend<I>(&mut self, iter: I)
     where
-        I: ExactSizeIterator<Item = T>,
+        I: Iterator<Item = T>,
     {
         match self {
             SmallHeap::Stack(heap) => {
-                if heap.capacity() >= heap.len() + iter.len() {
+                // The upper bound suffices to guarantee that all items fit
+                let max_len = iter.size_hint().1.unwrap_or(usize::MAX);
+                if heap.capacity() - heap.len() >= max_len {
                     for item in iter {
                         if heap.push(item).is_err() {
                             unreachable!();
                         }
                     }
                 } else {
-                    let capacity = heap.len() + iter.len();
+                    let capacity = heap.len() + iter.size_hint().0;
                     let new_heap = self.spill(capacity);
                     new_heap.extend(iter);
                 }
@@ -229,25 +454,43 @@ impl<T: Ord> SmallHeap<T> {
     }
 }
 
-pub fn nearest_neighbor_with_distance_2<T>(
-    node: &ParentNode<T>,
-    query_point: <T::Envelope as Envelope>::Point,
-) -> Option<(&T, Distance<T>)>
+pub fn nearest_neighbor_with_distance_2<'a, T, Q>(
+    node: &'a ParentNode<T>,
+    query: &Q,
+) -> Option<(&'a T, Distance<T>)>
 where
-    T: PointDistance,
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
 {
-    fn extend_heap<'a, T>(
+    nearest_neighbor_within_distance_2(node, query, Bounded::max_value())
+}
+
+/// Returns the nearest neighbor if its distance is less than or equal to `max_distance_2`.
+///
+/// A good initial bound, e.g. the distance to a neighbor of a nearby query, allows
+/// to prune most nodes early on.
+pub fn nearest_neighbor_within_distance_2<'a, T, Q>(
+    node: &'a ParentNode<T>,
+    query: &Q,
+    max_distance_2: Distance<T>,
+) -> Option<(&'a T, Distance<T>)>
+where
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
+{
+    fn extend_heap<'a, T, Q>(
         nodes: &mut SmallHeap<RTreeNodeDistanceWrapper<'a, T>>,
         node: &'a ParentNode<T>,
-        query_point: &<T::Envelope as Envelope>::Point,
+        query: &Q,
         min_max_distance: &mut Distance<T>,
     ) where
-        T: PointDistance + 'a,
+        T: RTreeObject + 'a,
+        Q: NearestNeighborQuery<T>,
     {
         for child in &node.children {
             let distance_if_less_or_equal = match child {
                 RTreeNode::Parent(ref data) => {
-                    let distance = data.envelope.distance_2(query_point);
+                    let distance = query.envelope_distance_2(&data.envelope);
                     if distance <= *min_max_distance {
                         Some(distance)
                     } else {
@@ -255,13 +498,13 @@ where
                     }
                 }
                 RTreeNode::Leaf(ref t) => {
-                    t.distance_2_if_less_or_equal(query_point, *min_max_distance)
+                    query.object_distance_2_if_less_or_equal(t, *min_max_distance)
                 }
             };
             if let Some(distance) = distance_if_less_or_equal {
                 *min_max_distance = min_inline(
                     *min_max_distance,
-                    child.envelope().min_max_dist_2(query_point),
+                    query.envelope_min_max_distance_2(&child.envelope()),
                 );
                 nodes.push(RTreeNodeDistanceWrapper {
                     node: child,
@@ -272,16 +515,16 @@ where
     }
 
     // Calculate smallest minmax-distance
-    let mut smallest_min_max: Distance<T> = Bounded::max_value();
+    let mut smallest_min_max: Distance<T> = max_distance_2;
     let mut nodes = SmallHeap::new();
-    extend_heap(&mut nodes, node, &query_point, &mut smallest_min_max);
+    extend_heap(&mut nodes, node, query, &mut smallest_min_max);
     while let Some(current) = nodes.pop() {
         match current {
             RTreeNodeDistanceWrapper {
                 node: RTreeNode::Parent(ref data),
                 ..
             } => {
-                extend_heap(&mut nodes, data, &query_point, &mut smallest_min_max);
+                extend_heap(&mut nodes, data, query, &mut smallest_min_max);
             }
             RTreeNodeDistanceWrapper {
                 node: RTreeNode::Leaf(ref t),
@@ -294,14 +537,63 @@ where
     None
 }
 
-pub fn nearest_neighbors_with_distance_2<T>(
+/// Orders objects by their distance to a query, while ranking nodes by their distance
+/// multiplied by a factor.
+///
+/// An object is thus returned before any node that cannot contain an object closer than
+/// the object's distance divided by the factor.
+pub(crate) struct ApproximateNearestNeighborFunction<Q, D> {
+    query: Q,
+    factor: D,
+}
+
+impl<T, Q> PrunedBestFirstFunction<T> for ApproximateNearestNeighborFunction<Q, Distance<T>>
+where
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
+{
+    type Key = Distance<T>;
+
+    fn node_key(&self, envelope: &T::Envelope) -> Option<Distance<T>> {
+        Some(self.query.envelope_distance_2(envelope) * self.factor)
+    }
+
+    fn object_key(&self, object: &T) -> Option<Distance<T>> {
+        Some(self.query.object_distance_2(object))
+    }
+}
+
+/// Returns up to `k` approximate nearest neighbors, sorted by their distance.
+///
+/// Each returned object's distance is at most `factor` times the distance of any object
+/// that is not returned.
+pub fn k_nearest_neighbors_approx<T, Q>(
     node: &ParentNode<T>,
-    query_point: <T::Envelope as Envelope>::Point,
+    query: Q,
+    k: usize,
+    factor: Distance<T>,
+) -> Vec<(&T, Distance<T>)>
+where
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
+{
+    let function = ApproximateNearestNeighborFunction { query, factor };
+    let mut result: Vec<_> = BestFirstSearch::new(node, function).take(k).collect();
+    // Objects found in a node ranked by its inflated distance may be closer than
+    // previously returned objects
+    result.sort_by(|(_, l), (_, r)| l.partial_cmp(r).unwrap());
+    result
+}
+
+pub fn nearest_neighbors_with_distance_2<T, Q>(
+    node: &ParentNode<T>,
+    query: Q,
 ) -> Option<(Vec<&T>, Distance<T>)>
 where
-    T: PointDistance,
+    T: RTreeObject,
+    Q: NearestNeighborQuery<T>,
 {
-    let mut nearest_neighbors = NearestNeighborDistance2Iterator::new(node, query_point);
+    let mut nearest_neighbors = NearestNeighborDistance2Iterator::new(node, query);
 
     let (first, first_distance_2) = nearest_neighbors.next()?;
 
@@ -323,8 +615,38 @@ where
 #[cfg(test)]
 mod test {
     use crate::object::PointDistance;
+    use crate::primitives::{GeomWithData, Line, Rectangle};
     use crate::rtree::RTree;
     use crate::test_utilities::*;
+    use crate::RTreeObject;
+
+    #[test]
+    fn test_locate_within_distance_sorted() {
+        let points = create_random_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+        for query_point in create_random_points(20, SEED_2) {
+            let mut expected: Vec<_> = points
+                .iter()
+                .map(|p| p.distance_2(&query_point))
+                .filter(|distance_2| *distance_2 <= 0.01)
+                .collect();
+            expected.sort_by(|l, r| l.partial_cmp(r).unwrap());
+            let result: Vec<_> = tree
+                .locate_within_distance_sorted(query_point, 0.01)
+                .map(|(point, distance_2)| {
+                    assert_eq!(point.distance_2(&query_point), distance_2);
+                    distance_2
+                })
+                .collect();
+            assert_eq!(result, expected);
+        }
+
+        let empty: RTree<[f64; 2]> = RTree::new();
+        assert_eq!(
+            empty.locate_within_distance_sorted([0.0, 0.0], 1.0).count(),
+            0
+        );
+    }
 
     #[test]
     fn test_nearest_neighbor_empty() {
@@ -411,4 +733,224 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_nearest_neighbor_to_line() {
+        let points = create_random_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+
+        for query in create_random_lines(50, SEED_2) {
+            let closest_distance_2 = points
+                .iter()
+                .map(|point| query.distance_2(point))
+                .fold(f64::INFINITY, f64::min);
+            let (nearest, distance_2) = tree.nearest_neighbor_to_with_distance_2(query).unwrap();
+            assert_eq!(distance_2, closest_distance_2);
+            assert_eq!(query.distance_2(nearest), closest_distance_2);
+            assert_eq!(tree.nearest_neighbor_to(query), Some(nearest));
+        }
+    }
+
+    #[test]
+    fn test_nearest_neighbor_iter_to_aabb() {
+        let lines = create_random_lines(1000, SEED_1);
+        let tree = RTree::bulk_load(lines.clone());
+
+        for query in create_random_rectangles(20, SEED_2) {
+            let query = query.envelope();
+            let mut distances: Vec<_> = lines
+                .iter()
+                .map(|line| line.distance_2_to_aabb(&query))
+                .collect();
+            distances.sort_by(|l, r| l.partial_cmp(r).unwrap());
+            let collected: Vec<_> = tree
+                .nearest_neighbor_iter_to_with_distance_2(query)
+                .map(|(_, distance_2)| distance_2)
+                .collect();
+            assert_eq!(distances, collected);
+            assert_eq!(tree.nearest_neighbor_iter_to(query).count(), lines.len());
+        }
+    }
+
+    #[test]
+    fn test_nearest_neighbor_to_with_data() {
+        let lines = create_random_lines(1000, SEED_1);
+        let tree = RTree::bulk_load(
+            lines
+                .iter()
+                .enumerate()
+                .map(|(index, line)| GeomWithData::new(*line, index))
+                .collect(),
+        );
+
+        for query in create_random_lines(20, SEED_2) {
+            let closest_distance_2 = lines
+                .iter()
+                .map(|line| query.distance_2_to_line(line))
+                .fold(f64::INFINITY, f64::min);
+            let (nearest, distance_2) = tree.nearest_neighbor_to_with_distance_2(query).unwrap();
+            assert_eq!(distance_2, closest_distance_2);
+            assert_eq!(query.distance_2_to_line(&lines[nearest.data]), distance_2);
+
+            let rectangle = Rectangle::from_aabb(query.envelope());
+            let closest_distance_2 = lines
+                .iter()
+                .map(|line| line.distance_2_to_aabb(&query.envelope()))
+                .fold(f64::INFINITY, f64::min);
+            let (_, distance_2) = tree.nearest_neighbor_to_with_distance_2(rectangle).unwrap();
+            assert_eq!(distance_2, closest_distance_2);
+        }
+    }
+
+    #[test]
+    fn test_nearest_neighbor_to_empty() {
+        let tree: RTree<Line<[f64; 2]>> = RTree::new();
+        let query = Line::new([0.0, 0.0], [1.0, 1.0]);
+        assert!(tree.nearest_neighbor_to(query).is_none());
+        assert!(tree.nearest_neighbor_iter_to(query).next().is_none());
+    }
+
+    #[test]
+    fn test_nearest_neighbor_filtered() {
+        let points = create_random_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+        let predicate = |point: &[f64; 2]| point[0] > 0.5;
+
+        let sample_points = create_random_points(100, SEED_2);
+        for sample_point in sample_points {
+            let nearest = points
+                .iter()
+                .filter(|point| predicate(point))
+                .min_by(|l, r| {
+                    l.distance_2(&sample_point)
+                        .partial_cmp(&r.distance_2(&sample_point))
+                        .unwrap()
+                });
+            assert_eq!(
+                nearest,
+                tree.nearest_neighbor_filtered(sample_point, predicate)
+            );
+        }
+    }
+
+    #[test]
+    fn test_nearest_neighbor_iter_filtered() {
+        let mut points = create_random_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+        let predicate = |point: &[f64; 2]| point[1] < 0.0;
+
+        let sample_points = create_random_points(50, SEED_2);
+        for sample_point in sample_points {
+            points.sort_by(|r, l| {
+                r.distance_2(&sample_point)
+                    .partial_cmp(&l.distance_2(&sample_point))
+                    .unwrap()
+            });
+            let expected: Vec<_> = points.iter().filter(|p| predicate(p)).collect();
+            let collected: Vec<_> = tree
+                .nearest_neighbor_iter_filtered(sample_point, predicate)
+                .collect();
+            assert_eq!(expected, collected);
+        }
+    }
+
+    #[test]
+    fn test_nearest_neighbor_with_selection_function() {
+        use crate::{SelectionFunction, AABB};
+
+        struct RightOf(f64);
+
+        impl SelectionFunction<[f64; 2]> for RightOf {
+            fn should_unpack_parent(&self, envelope: &AABB<[f64; 2]>) -> bool {
+                envelope.upper()[0] > self.0
+            }
+
+            fn should_unpack_leaf(&self, point: &[f64; 2]) -> bool {
+                point[0] > self.0
+            }
+        }
+
+        let points = create_random_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+
+        let sample_points = create_random_points(100, SEED_2);
+        for sample_point in sample_points {
+            let nearest = points.iter().filter(|point| point[0] > 0.5).min_by(|l, r| {
+                l.distance_2(&sample_point)
+                    .partial_cmp(&r.distance_2(&sample_point))
+                    .unwrap()
+            });
+            assert_eq!(
+                nearest,
+                tree.nearest_neighbor_with_selection_function(sample_point, RightOf(0.5))
+            );
+        }
+
+        let empty: RTree<[f64; 2]> = RTree::new();
+        assert!(empty
+            .nearest_neighbor_with_selection_function([0.0, 0.0], RightOf(0.5))
+            .is_none());
+        assert!(tree
+            .nearest_neighbor_with_selection_function([0.0, 0.0], RightOf(10.0))
+            .is_none());
+    }
+
+    #[test]
+    fn test_nearest_neighbor_approx() {
+        let points = create_random_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+
+        for query_point in create_random_points(100, SEED_2) {
+            let exact = tree.nearest_neighbor(query_point).unwrap();
+            let exact_distance_2 = exact.distance_2(&query_point);
+            assert_eq!(
+                tree.nearest_neighbor_approx(query_point, 0.0)
+                    .unwrap()
+                    .distance_2(&query_point),
+                exact_distance_2
+            );
+            for epsilon in [0.1, 0.5, 2.0] {
+                let approx = tree.nearest_neighbor_approx(query_point, epsilon).unwrap();
+                let bound = (1.0 + epsilon) * (1.0 + epsilon) * exact_distance_2;
+                assert!(approx.distance_2(&query_point) <= bound);
+            }
+        }
+
+        let empty: RTree<[f64; 2]> = RTree::new();
+        assert!(empty.nearest_neighbor_approx([0.0, 0.0], 0.5).is_none());
+    }
+
+    #[test]
+    fn test_k_nearest_neighbors_approx() {
+        let points = create_random_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+
+        for query_point in create_random_points(50, SEED_2) {
+            let exact: Vec<_> = tree
+                .nearest_neighbor_iter_with_distance_2(query_point)
+                .map(|(_, distance_2)| distance_2)
+                .take(10)
+                .collect();
+            for k in [0, 1, 10] {
+                let distances: Vec<_> = tree
+                    .k_nearest_neighbors_approx(query_point, k, 0.0)
+                    .iter()
+                    .map(|p| p.distance_2(&query_point))
+                    .collect();
+                assert_eq!(distances, exact[..k]);
+            }
+            let epsilon = 0.3;
+            let approx = tree.k_nearest_neighbors_approx(query_point, 10, epsilon);
+            assert_eq!(approx.len(), 10);
+            for (point, exact_distance_2) in approx.iter().zip(&exact) {
+                let bound = (1.0 + epsilon) * (1.0 + epsilon) * exact_distance_2;
+                assert!(point.distance_2(&query_point) <= bound);
+            }
+        }
+
+        assert_eq!(
+            tree.k_nearest_neighbors_approx([0.5, 0.5], 2000, 1.0).len(),
+            1000
+        );
+    }
 }
@@ -0,0 +1,240 @@
+use crate::algorithm::nearest_neighbor::{NearestNeighborQuery, RTreeNodeDistanceWrapper};
+use crate::node::{ParentNode, RTreeNode};
+use crate::object::Distance;
+use crate::point::max_inline;
+use crate::{Envelope, Point, RTreeObject, AABB};
+
+#[cfg(doc)]
+use crate::RTree;
+
+use alloc::collections::BinaryHeap;
+#[cfg(not(test))]
+use alloc::{vec, vec::Vec};
+use num_traits::Zero;
+
+type JoinResult<'a, T, U> = (&'a T, Vec<(&'a U, Distance<T>)>);
+
+/// Iterator returned by [`RTree::k_nearest_neighbors_join`].
+///
+/// The leaves of the first tree are processed in groups sharing the same parent node, and a
+/// single best-first search from the root of the second tree is run for each group. Nodes of
+/// the second tree are skipped as soon as they are farther away from the group's envelope
+/// than the current `k`-th nearest neighbor of every group member. Pairs of inner nodes are
+/// not pruned against each other.
+pub struct KNearestNeighborsJoinIterator<'a, T, U = T>
+where
+    T: RTreeObject,
+    U: RTreeObject<Envelope = T::Envelope>,
+{
+    todo_list: Vec<&'a ParentNode<T>>,
+    other_root: &'a ParentNode<U>,
+    k: usize,
+    results: alloc::vec::IntoIter<JoinResult<'a, T, U>>,
+}
+
+impl<'a, T, U, P> KNearestNeighborsJoinIterator<'a, T, U>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>> + NearestNeighborQuery<U>,
+    U: RTreeObject<Envelope = AABB<P>>,
+{
+    pub(crate) fn new(root: &'a ParentNode<T>, other_root: &'a ParentNode<U>, k: usize) -> Self {
+        KNearestNeighborsJoinIterator {
+            todo_list: vec![root],
+            other_root,
+            k,
+            results: Vec::new().into_iter(),
+        }
+    }
+
+    fn join_group(&self, group: &mut [JoinResult<'a, T, U>]) {
+        if self.k == 0 {
+            return;
+        }
+        let envelope = group
+            .iter()
+            .fold(T::Envelope::new_empty(), |mut envelope, (object, _)| {
+                envelope.merge(&object.envelope());
+                envelope
+            });
+
+        let mut heap = BinaryHeap::new();
+        push_children(&mut heap, &envelope, self.other_root, None);
+
+        while let Some(RTreeNodeDistanceWrapper { node, distance }) = heap.pop() {
+            let max_distance_2 = self.max_neighbor_distance_2(group);
+            if max_distance_2.is_some_and(|max_distance_2| distance > max_distance_2) {
+                // All remaining nodes are even farther away
+                break;
+            }
+            match node {
+                RTreeNode::Parent(parent) => {
+                    push_children(&mut heap, &envelope, parent, max_distance_2);
+                }
+                RTreeNode::Leaf(other) => {
+                    for (object, neighbors) in group.iter_mut() {
+                        insert_neighbor(*object, other, neighbors, self.k);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the largest `k`-th neighbor distance within a group, or `None` if any group
+    /// member has found less than `k` neighbors so far.
+    fn max_neighbor_distance_2(&self, group: &[JoinResult<'a, T, U>]) -> Option<Distance<T>> {
+        let mut result = Zero::zero();
+        for (_, neighbors) in group {
+            let (_, distance_2) = neighbors.get(self.k - 1)?;
+            result = max_inline(result, *distance_2);
+        }
+        Some(result)
+    }
+}
+
+fn push_children<'a, U, P>(
+    heap: &mut BinaryHeap<RTreeNodeDistanceWrapper<'a, U>>,
+    envelope: &AABB<P>,
+    parent: &'a ParentNode<U>,
+    max_distance_2: Option<P::Scalar>,
+) where
+    P: Point,
+    U: RTreeObject<Envelope = AABB<P>>,
+{
+    for child in parent.children() {
+        let distance = envelope.distance_2_to_aabb(&child.envelope());
+        if max_distance_2.is_none_or(|max_distance_2| distance <= max_distance_2) {
+            heap.push(RTreeNodeDistanceWrapper {
+                node: child,
+                distance,
+            });
+        }
+    }
+}
+
+fn insert_neighbor<'a, T, U>(
+    object: &T,
+    other: &'a U,
+    neighbors: &mut Vec<(&'a U, Distance<U>)>,
+    k: usize,
+) where
+    T: NearestNeighborQuery<U>,
+    U: RTreeObject,
+{
+    let distance_2 = if neighbors.len() < k {
+        Some(object.object_distance_2(other))
+    } else {
+        object.object_distance_2_if_less_or_equal(other, neighbors[k - 1].1)
+    };
+    if let Some(distance_2) = distance_2 {
+        let index = neighbors.partition_point(|(_, d)| *d <= distance_2);
+        if index < k {
+            neighbors.truncate(k - 1);
+            neighbors.insert(index, (other, distance_2));
+        }
+    }
+}
+
+impl<'a, T, U, P> Iterator for KNearestNeighborsJoinIterator<'a, T, U>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>> + NearestNeighborQuery<U>,
+    U: RTreeObject<Envelope = AABB<P>>,
+{
+    type Item = JoinResult<'a, T, U>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(result) = self.results.next() {
+                return Some(result);
+            }
+            let node = self.todo_list.pop()?;
+            let mut group = Vec::new();
+            for child in node.children() {
+                match child {
+                    RTreeNode::Leaf(object) => group.push((object, Vec::new())),
+                    RTreeNode::Parent(parent) => self.todo_list.push(parent),
+                }
+            }
+            self.join_group(&mut group);
+            self.results = group.into_iter();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::object::PointDistance;
+    use crate::primitives::{GeomWithData, Line};
+    use crate::test_utilities::*;
+    use crate::RTree;
+
+    #[test]
+    fn test_k_nearest_neighbors_join() {
+        let points1 = create_random_points(500, SEED_1);
+        let points2 = create_random_points(300, SEED_2);
+        let tree1 = RTree::bulk_load(points1.clone());
+        let tree2 = RTree::bulk_load(points2.clone());
+
+        for k in [0, 1, 3, 10] {
+            let mut count = 0;
+            for (point, neighbors) in tree1.k_nearest_neighbors_join(&tree2, k) {
+                count += 1;
+                let mut expected: Vec<_> = points2.iter().map(|p| p.distance_2(point)).collect();
+                expected.sort_by(|l, r| l.partial_cmp(r).unwrap());
+                expected.truncate(k);
+
+                let distances: Vec<_> = neighbors.iter().map(|(_, d)| *d).collect();
+                assert_eq!(distances, expected);
+                for (neighbor, distance_2) in neighbors {
+                    assert_eq!(neighbor.distance_2(point), distance_2);
+                }
+            }
+            assert_eq!(count, points1.len());
+        }
+    }
+
+    #[test]
+    fn test_k_nearest_neighbors_join_more_than_size() {
+        let points1 = create_random_points(50, SEED_1);
+        let points2 = create_random_points(5, SEED_2);
+        let tree1 = RTree::bulk_load(points1);
+        let tree2 = RTree::bulk_load(points2);
+
+        for (_, neighbors) in tree1.k_nearest_neighbors_join(&tree2, 10) {
+            assert_eq!(neighbors.len(), 5);
+        }
+    }
+
+    #[test]
+    fn test_k_nearest_neighbors_join_empty() {
+        let points = create_random_points(50, SEED_1);
+        let tree = RTree::bulk_load(points);
+        let empty: RTree<[f64; 2]> = RTree::new();
+
+        assert_eq!(empty.k_nearest_neighbors_join(&tree, 3).count(), 0);
+        let results: Vec<_> = tree.k_nearest_neighbors_join(&empty, 3).collect();
+        assert_eq!(results.len(), 50);
+        assert!(results.iter().all(|(_, neighbors)| neighbors.is_empty()));
+    }
+
+    #[test]
+    fn test_k_nearest_neighbors_join_with_data() {
+        let addresses: Vec<_> = create_random_points(200, SEED_1)
+            .into_iter()
+            .enumerate()
+            .map(|(index, point)| GeomWithData::new(point, index))
+            .collect();
+        let lines = create_random_lines(100, SEED_2);
+        let tree1 = RTree::bulk_load(addresses);
+        let tree2: RTree<Line<[f64; 2]>> = RTree::bulk_load(lines.clone());
+
+        for (address, neighbors) in tree1.k_nearest_neighbors_join(&tree2, 3) {
+            let mut expected: Vec<_> = lines.iter().map(|l| l.distance_2(address.geom())).collect();
+            expected.sort_by(|l, r| l.partial_cmp(r).unwrap());
+            expected.truncate(3);
+            let distances: Vec<_> = neighbors.iter().map(|(_, d)| *d).collect();
+            assert_eq!(distances, expected);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use crate::algorithm::iterators::visit_parent;
+use crate::algorithm::selection_functions::SelectionFunction;
+use crate::node::{ParentNode, RTreeNode};
+use crate::object::RTreeObject;
+
+#[cfg(doc)]
+use crate::RTree;
+
+#[cfg(not(test))]
+use alloc::vec::Vec;
+use rayon::iter::plumbing::{bridge_unindexed, Folder, UnindexedConsumer, UnindexedProducer};
+use rayon::iter::ParallelIterator;
+
+/// Parallel iterator returned by `RTree::par_locate_*` methods.
+///
+/// The work is split at the top levels of the tree: each task receives a set of
+/// subtrees and traverses them sequentially.
+pub struct ParSelectionIterator<'a, T, Func>
+where
+    T: RTreeObject + 'a,
+    Func: SelectionFunction<T>,
+{
+    root: &'a ParentNode<T>,
+    func: Func,
+}
+
+impl<'a, T, Func> ParSelectionIterator<'a, T, Func>
+where
+    T: RTreeObject,
+    Func: SelectionFunction<T>,
+{
+    pub(crate) fn new(root: &'a ParentNode<T>, func: Func) -> Self {
+        ParSelectionIterator { root, func }
+    }
+}
+
+impl<'a, T, Func> ParallelIterator for ParSelectionIterator<'a, T, Func>
+where
+    T: RTreeObject + Sync,
+    T::Envelope: Sync,
+    Func: SelectionFunction<T> + Send + Sync,
+{
+    type Item = &'a T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let producer = SelectionProducer {
+            nodes: unpack(self.root, false, &self.func),
+            func: &self.func,
+        };
+        bridge_unindexed(producer, consumer)
+    }
+}
+
+/// Returns the children of `parent` that need to be visited, together with a flag
+/// indicating if all elements contained within a child are selected.
+///
+/// `selected` indicates if all elements contained within `parent` are selected.
+fn unpack<'a, T, Func>(
+    parent: &'a ParentNode<T>,
+    selected: bool,
+    func: &Func,
+) -> Vec<(&'a RTreeNode<T>, bool)>
+where
+    T: RTreeObject,
+    Func: SelectionFunction<T>,
+{
+    match visit_parent(func, &parent.envelope, selected) {
+        Some(selected) => parent
+            .children
+            .iter()
+            .map(|child| (child, selected))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Sequentially feeds all selected elements below `node` into `folder`.
+fn fold_node<'a, T, Func, F>(node: &'a RTreeNode<T>, selected: bool, func: &Func, folder: F) -> F
+where
+    T: RTreeObject,
+    Func: SelectionFunction<T>,
+    F: Folder<&'a T>,
+{
+    match node {
+        RTreeNode::Leaf(ref t) => {
+            if selected || func.should_unpack_leaf(t) {
+                folder.consume(t)
+            } else {
+                folder
+            }
+        }
+        RTreeNode::Parent(ref data) => {
+            let mut folder = folder;
+            for (child, selected) in unpack(data, selected, func) {
+                folder = fold_node(child, selected, func, folder);
+                if folder.full() {
+                    break;
+                }
+            }
+            folder
+        }
+    }
+}
+
+struct SelectionProducer<'a, 'f, T, Func>
+where
+    T: RTreeObject,
+{
+    /// Nodes which still need to be visited. Contained leaves are only checked by
+    /// the selection function if the flag is not set.
+    nodes: Vec<(&'a RTreeNode<T>, bool)>,
+    func: &'f Func,
+}
+
+impl<'a, T, Func> UnindexedProducer for SelectionProducer<'a, '_, T, Func>
+where
+    T: RTreeObject + Sync,
+    T::Envelope: Sync,
+    Func: SelectionFunction<T> + Sync,
+{
+    type Item = &'a T;
+
+    fn split(mut self) -> (Self, Option<Self>) {
+        // Descend into a single remaining subtree to obtain more nodes to split
+        while let [(RTreeNode::Parent(ref data), selected)] = self.nodes[..] {
+            self.nodes = unpack(data, selected, self.func);
+        }
+        if self.nodes.len() < 2 {
+            return (self, None);
+        }
+        let right = SelectionProducer {
+            nodes: self.nodes.split_off(self.nodes.len() / 2),
+            func: self.func,
+        };
+        (self, Some(right))
+    }
+
+    fn fold_with<F>(self, mut folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        for (node, selected) in self.nodes {
+            folder = fold_node(node, selected, self.func, folder);
+            if folder.full() {
+                break;
+            }
+        }
+        folder
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::algorithm::selection_functions::SelectionFunction;
+    use crate::object::PointDistance;
+    use crate::test_utilities::*;
+    use crate::{RTree, RTreeObject, AABB};
+    use rayon::iter::ParallelIterator;
+
+    fn sorted(mut points: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+        points.sort_by(|l, r| l.partial_cmp(r).unwrap());
+        points
+    }
+
+    #[test]
+    fn test_par_iter() {
+        let points = create_random_points(5000, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+        assert_eq!(sorted(tree.par_iter().copied().collect()), sorted(points));
+
+        let empty: RTree<[f64; 2]> = RTree::new();
+        assert_eq!(empty.par_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_par_locate_in_envelope() {
+        let tree = RTree::bulk_load(create_random_points(5000, SEED_1));
+        for envelope in create_random_rectangles(20, SEED_2) {
+            let envelope = envelope.envelope();
+            let expected: Vec<_> = tree.locate_in_envelope(envelope).copied().collect();
+            let result: Vec<_> = tree.par_locate_in_envelope(envelope).copied().collect();
+            assert_eq!(sorted(result), sorted(expected));
+        }
+        let everything = AABB::from_corners([-1.0, -1.0], [2.0, 2.0]);
+        assert_eq!(tree.par_locate_in_envelope(everything).count(), tree.size());
+    }
+
+    #[test]
+    fn test_par_locate_with_selection_function() {
+        struct WithinDistance {
+            center: [f64; 2],
+            squared_radius: f64,
+        }
+
+        impl SelectionFunction<[f64; 2]> for WithinDistance {
+            fn should_unpack_parent(&self, envelope: &AABB<[f64; 2]>) -> bool {
+                envelope.distance_2(&self.center) <= self.squared_radius
+            }
+
+            fn should_unpack_leaf(&self, leaf: &[f64; 2]) -> bool {
+                leaf.distance_2(&self.center) <= self.squared_radius
+            }
+        }
+
+        let tree = RTree::bulk_load(create_random_points(5000, SEED_1));
+        for center in create_random_points(20, SEED_2) {
+            let expected: Vec<_> = tree.locate_within_distance(center, 0.01).copied().collect();
+            let result: Vec<_> = tree
+                .par_locate_with_selection_function(WithinDistance {
+                    center,
+                    squared_radius: 0.01,
+                })
+                .copied()
+                .collect();
+            assert_eq!(sorted(result), sorted(expected));
+        }
+    }
+}
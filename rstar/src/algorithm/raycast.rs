@@ -0,0 +1,157 @@
+use crate::algorithm::best_first::{BestFirstSearch, PrunedBestFirstFunction};
+use crate::node::ParentNode;
+use crate::object::Distance;
+use crate::primitives::{Ray, RayIntersect};
+use crate::{Envelope, Point, RTreeObject, AABB};
+use num_traits::Float;
+
+#[cfg(doc)]
+use crate::RTree;
+
+type IntersectionFunction<T> =
+    fn(&T, &Ray<<<T as RTreeObject>::Envelope as Envelope>::Point>) -> Option<Distance<T>>;
+
+/// Orders objects by the ray parameter at which they are hit, skipping all misses.
+pub(crate) struct RaycastFunction<T>
+where
+    T: RTreeObject,
+{
+    ray: Ray<<T::Envelope as Envelope>::Point>,
+    intersection: IntersectionFunction<T>,
+}
+
+impl<T, P> PrunedBestFirstFunction<T> for RaycastFunction<T>
+where
+    P: Point,
+    P::Scalar: Float,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    type Key = P::Scalar;
+
+    fn node_key(&self, envelope: &AABB<P>) -> Option<P::Scalar> {
+        self.ray.aabb_intersection(envelope)
+    }
+
+    fn object_key(&self, object: &T) -> Option<P::Scalar> {
+        (self.intersection)(object, &self.ray)
+    }
+}
+
+/// Iterator returned by [`RTree::raycast`] and [`RTree::raycast_exact`].
+///
+/// Yields `(object, t)` tuples sorted by the ray parameter `t` at which each object is hit.
+pub struct RaycastIterator<'a, T>
+where
+    T: RTreeObject + 'a,
+{
+    search: BestFirstSearch<'a, T, RaycastFunction<T>, Distance<T>>,
+}
+
+impl<'a, T, P> RaycastIterator<'a, T>
+where
+    P: Point,
+    P::Scalar: Float,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    pub(crate) fn new(root: &'a ParentNode<T>, ray: Ray<P>) -> Self {
+        Self::new_with_intersection(root, ray, |object, ray| {
+            ray.aabb_intersection(&object.envelope())
+        })
+    }
+
+    pub(crate) fn new_exact(root: &'a ParentNode<T>, ray: Ray<P>) -> Self
+    where
+        T: RayIntersect,
+    {
+        Self::new_with_intersection(root, ray, T::ray_intersection)
+    }
+
+    fn new_with_intersection(
+        root: &'a ParentNode<T>,
+        ray: Ray<P>,
+        intersection: IntersectionFunction<T>,
+    ) -> Self {
+        RaycastIterator {
+            search: BestFirstSearch::new(root, RaycastFunction { ray, intersection }),
+        }
+    }
+}
+
+impl<'a, T, P> Iterator for RaycastIterator<'a, T>
+where
+    P: Point,
+    P::Scalar: Float,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    type Item = (&'a T, P::Scalar);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.search.next()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::primitives::{Line, Ray, RayIntersect, Rectangle};
+    use crate::test_utilities::*;
+    use crate::{RTree, RTreeObject};
+
+    #[test]
+    fn test_raycast() {
+        let rectangles = create_random_rectangles(500, SEED_1);
+        let tree = RTree::bulk_load(rectangles.clone());
+
+        let origins = create_random_points(20, SEED_2);
+        let directions = create_random_points(20, SEED_1);
+        for (origin, direction) in origins.into_iter().zip(directions) {
+            let ray = Ray::new(origin, [direction[0] - 0.5, direction[1] - 0.5]);
+            let mut expected: Vec<_> = rectangles
+                .iter()
+                .filter_map(|r| Some((r, ray.aabb_intersection(&r.envelope())?)))
+                .collect();
+            expected.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+            let hits: Vec<_> = tree.raycast(ray).collect();
+            assert_eq!(hits.len(), expected.len());
+            for ((_, t), (_, expected_t)) in hits.iter().zip(&expected) {
+                assert_eq!(t, expected_t);
+            }
+            let exact_hits: Vec<_> = tree.raycast_exact(ray).collect();
+            assert_eq!(hits, exact_hits);
+        }
+    }
+
+    #[test]
+    fn test_raycast_exact() {
+        let lines = create_random_lines(500, SEED_1);
+        let tree = RTree::bulk_load(lines.clone());
+
+        let origins = create_random_points(20, SEED_2);
+        let mut total = 0;
+        for origin in origins {
+            let ray = Ray::new(origin, [1.0, 0.25]);
+            let mut expected: Vec<_> = lines
+                .iter()
+                .filter_map(|l| l.ray_intersection(&ray))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let hits: Vec<_> = tree.raycast_exact(ray).map(|(_, t)| t).collect();
+            assert_eq!(hits, expected);
+            assert!(tree.raycast(ray).count() >= hits.len());
+            total += hits.len();
+        }
+        assert!(total > 0);
+    }
+
+    #[test]
+    fn test_raycast_empty() {
+        let tree: RTree<Line<[f64; 2]>> = RTree::new();
+        let ray = Ray::new([0.0, 0.0], [1.0, 0.0]);
+        assert_eq!(tree.raycast(ray).count(), 0);
+        assert_eq!(tree.raycast_exact(ray).count(), 0);
+
+        let tree = RTree::bulk_load(vec![Rectangle::from_corners([1.0, 1.0], [2.0, 2.0])]);
+        assert_eq!(tree.raycast(Ray::new([0.0, 0.0], [-1.0, 0.0])).count(), 0);
+    }
+}
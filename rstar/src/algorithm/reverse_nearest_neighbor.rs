@@ -0,0 +1,200 @@
+use crate::algorithm::iterators::SelectionIterator;
+use crate::algorithm::nearest_neighbor::RTreeNodeDistanceWrapper;
+use crate::algorithm::selection_functions::SelectWithinDistanceFunction;
+use crate::node::{ParentNode, RTreeNode};
+use crate::point::{max_inline, PointExt};
+use crate::{Point, PointDistance, PointObject, RTreeObject, AABB};
+
+use alloc::collections::BinaryHeap;
+#[cfg(not(test))]
+use alloc::vec::Vec;
+use num_traits::{One, Zero};
+
+/// Returns true if every point within `envelope` is strictly closer to `point` than to
+/// `query_point`.
+///
+/// This is the case if `envelope` lies entirely within the half space bounded by the
+/// perpendicular bisector of both points.
+fn is_closer_everywhere<P: Point>(envelope: &AABB<P>, point: &P, query_point: &P) -> bool {
+    // For any o, |o - point|² - |o - query_point|² equals 2 (o - point)·d - |d|² with
+    // d = query_point - point. This is linear in o and thus maximal at a corner.
+    let two = P::Scalar::one() + P::Scalar::one();
+    let direction = query_point.sub(point);
+    let lower = envelope.lower().sub(point);
+    let upper = envelope.upper().sub(point);
+    let mut max = P::Scalar::zero();
+    for i in 0..P::DIMENSIONS {
+        let d = direction.nth(i);
+        max = max + max_inline(lower.nth(i) * d, upper.nth(i) * d);
+    }
+    max * two < direction.length_2()
+}
+
+/// Returns true if at least `k` of the given candidates are closer to every point within
+/// `envelope` than `query_point` is.
+fn is_pruned<T, P>(envelope: &AABB<P>, candidates: &[(&T, P)], query_point: &P, k: usize) -> bool
+where
+    P: Point,
+{
+    candidates
+        .iter()
+        .filter(|(_, position)| is_closer_everywhere(envelope, position, query_point))
+        .take(k)
+        .count()
+        == k
+}
+
+pub fn reverse_nearest_neighbors<T, P>(root: &ParentNode<T>, query_point: P, k: usize) -> Vec<&T>
+where
+    P: Point,
+    T: PointObject + PointDistance + RTreeObject<Envelope = AABB<P>>,
+{
+    let mut result = Vec::new();
+    if k == 0 {
+        return result;
+    }
+
+    // Filter step: visit all nodes by increasing distance to the query point. Previously found
+    // candidates are used to prune subtrees whose objects have k or more objects closer to
+    // them than the query point.
+    let mut candidates = Vec::new();
+    let mut heap = BinaryHeap::new();
+    heap.extend(
+        root.children()
+            .iter()
+            .map(|child| RTreeNodeDistanceWrapper {
+                node: child,
+                distance: child.envelope().distance_2(&query_point),
+            }),
+    );
+    while let Some(RTreeNodeDistanceWrapper { node, .. }) = heap.pop() {
+        if is_pruned(&node.envelope(), &candidates, &query_point, k) {
+            continue;
+        }
+        match node {
+            RTreeNode::Parent(parent) => {
+                heap.extend(
+                    parent
+                        .children()
+                        .iter()
+                        .map(|child| RTreeNodeDistanceWrapper {
+                            node: child,
+                            distance: child.envelope().distance_2(&query_point),
+                        }),
+                );
+            }
+            RTreeNode::Leaf(object) => candidates.push((object, object.position())),
+        }
+    }
+
+    // Refinement step: a candidate is a result if less than k other objects are closer
+    // to it than the query point. Only the objects within that distance are visited.
+    for (candidate, position) in candidates {
+        let query_distance_2 = candidate.distance_2(&query_point);
+        let selection_function =
+            SelectWithinDistanceFunction::new(position.clone(), query_distance_2);
+        let closer_objects = SelectionIterator::new(root, selection_function)
+            .filter(|object| {
+                !core::ptr::eq(*object, candidate)
+                    && object.distance_2(&position) < query_distance_2
+            })
+            .take(k)
+            .count();
+        if closer_objects < k {
+            result.push(candidate);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::object::PointDistance;
+    use crate::primitives::GeomWithData;
+    use crate::test_utilities::*;
+    use crate::RTree;
+
+    fn reverse_nearest_neighbors_brute_force(
+        points: &[[f64; 2]],
+        query_point: [f64; 2],
+        k: usize,
+    ) -> Vec<[f64; 2]> {
+        let mut result = Vec::new();
+        for (index, point) in points.iter().enumerate() {
+            let query_distance_2 = point.distance_2(&query_point);
+            let closer_points = points
+                .iter()
+                .enumerate()
+                .filter(|(other_index, other)| {
+                    *other_index != index && point.distance_2(other) < query_distance_2
+                })
+                .count();
+            if closer_points < k {
+                result.push(*point);
+            }
+        }
+        result.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        result
+    }
+
+    #[test]
+    fn test_reverse_nearest_neighbors() {
+        let points = create_random_points(500, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+
+        let query_points = create_random_points(20, SEED_2);
+        let mut total = 0;
+        for k in [0, 1, 2, 5] {
+            for query_point in &query_points {
+                let mut result: Vec<_> = tree
+                    .reverse_nearest_neighbors(*query_point, k)
+                    .into_iter()
+                    .cloned()
+                    .collect();
+                result.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                let expected = reverse_nearest_neighbors_brute_force(&points, *query_point, k);
+                assert_eq!(result, expected);
+                total += result.len();
+            }
+        }
+        assert!(total > 0);
+    }
+
+    #[test]
+    fn test_reverse_nearest_neighbors_with_duplicates() {
+        let points = vec![[0.0, 0.0], [0.0, 0.0], [1.0, 0.0]];
+        let tree = RTree::bulk_load(points.clone());
+
+        for k in 1..4 {
+            let result = tree.reverse_nearest_neighbors([0.5, 0.0], k);
+            let expected = reverse_nearest_neighbors_brute_force(&points, [0.5, 0.0], k);
+            assert_eq!(result.len(), expected.len());
+        }
+    }
+
+    #[test]
+    fn test_reverse_nearest_neighbors_with_data() {
+        let points = create_random_points(100, SEED_1);
+        let tree = RTree::bulk_load(
+            points
+                .iter()
+                .enumerate()
+                .map(|(index, point)| GeomWithData::new(*point, index))
+                .collect(),
+        );
+        let query_point = [0.5, 0.5];
+        let mut result: Vec<_> = tree
+            .reverse_nearest_neighbors(query_point, 3)
+            .into_iter()
+            .map(|point| *point.geom())
+            .collect();
+        result.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(
+            result,
+            reverse_nearest_neighbors_brute_force(&points, query_point, 3)
+        );
+
+        let empty: RTree<[f64; 2]> = RTree::new();
+        assert!(empty.reverse_nearest_neighbors(query_point, 3).is_empty());
+    }
+}
@@ -1,7 +1,16 @@
-use crate::object::PointDistance;
+use crate::metric::{rem_euclid, Metric};
 use crate::object::RTreeObject;
+use crate::object::{PointDistance, PointObject};
+use crate::primitives::{
+    spatial_envelope, HalfSpace, Line, Moving, MovingAABB, Temporal, TemporalPoint,
+};
 use crate::{envelope::Envelope, object::Distance};
 
+use crate::{Point, RTreeNum, AABB};
+#[cfg(not(test))]
+use alloc::vec::Vec;
+use num_traits::Float;
+
 /// Advanced trait to iterate through an r-tree. Usually it should not be required to be implemented.
 ///
 /// It is important to know some details about the inner structure of
@@ -34,6 +43,39 @@ where
     fn should_unpack_leaf(&self, _leaf: &T) -> bool {
         true
     }
+
+    /// Decides whether a parent node should be skipped, unpacked or selected as a whole.
+    ///
+    /// Returning [`ParentSelection::SelectAll`] allows searches to return a whole subtree
+    /// without calling [`should_unpack_parent`](Self::should_unpack_parent) and
+    /// [`should_unpack_leaf`](Self::should_unpack_leaf) for each contained node, e.g. when
+    /// the parent's envelope lies fully within a query region. This **must** imply that
+    /// these methods would return `true` for all nodes within the parent. Likewise,
+    /// [`ParentSelection::Skip`] must match `should_unpack_parent` returning `false`, as
+    /// some searches only call `should_unpack_parent`.
+    ///
+    /// The default implementation never selects a whole subtree and unpacks a parent if
+    /// `should_unpack_parent` returns `true`.
+    fn select_parent(&self, envelope: &T::Envelope) -> ParentSelection {
+        if self.should_unpack_parent(envelope) {
+            ParentSelection::Unpack
+        } else {
+            ParentSelection::Skip
+        }
+    }
+}
+
+/// Defines how a parent node is visited during a search.
+///
+/// Returned by [`SelectionFunction::select_parent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParentSelection {
+    /// The parent node and all contained elements are skipped.
+    Skip,
+    /// The parent node's children are checked individually.
+    Unpack,
+    /// All elements contained within the parent node are selected without further checks.
+    SelectAll,
 }
 
 pub struct SelectInEnvelopeFunction<T>
@@ -94,6 +136,526 @@ where
     }
 }
 
+/// Wraps a selection function that selects all elements contained within an envelope,
+/// additionally selecting any parent node that lies fully within this envelope.
+///
+/// Used by existence queries, which can stop at such a parent node without unpacking it.
+pub struct SelectContainedParentsFunction<T, Func>
+where
+    T: RTreeObject,
+{
+    envelope: T::Envelope,
+    func: Func,
+}
+
+impl<T, Func> SelectContainedParentsFunction<T, Func>
+where
+    T: RTreeObject,
+    Func: SelectionFunction<T>,
+{
+    pub fn new(envelope: T::Envelope, func: Func) -> Self {
+        SelectContainedParentsFunction { envelope, func }
+    }
+}
+
+impl<T, Func> SelectionFunction<T> for SelectContainedParentsFunction<T, Func>
+where
+    T: RTreeObject,
+    Func: SelectionFunction<T>,
+{
+    fn should_unpack_parent(&self, envelope: &T::Envelope) -> bool {
+        self.func.should_unpack_parent(envelope)
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        self.func.should_unpack_leaf(leaf)
+    }
+
+    fn select_parent(&self, envelope: &T::Envelope) -> ParentSelection {
+        if self.envelope.contains_envelope(envelope) {
+            ParentSelection::SelectAll
+        } else {
+            self.func.select_parent(envelope)
+        }
+    }
+}
+
+pub struct SelectContainingEnvelopeFunction<T>
+where
+    T: RTreeObject,
+{
+    envelope: T::Envelope,
+}
+
+impl<T> SelectContainingEnvelopeFunction<T>
+where
+    T: RTreeObject,
+{
+    pub fn new(envelope: T::Envelope) -> Self {
+        SelectContainingEnvelopeFunction { envelope }
+    }
+}
+
+impl<T> SelectionFunction<T> for SelectContainingEnvelopeFunction<T>
+where
+    T: RTreeObject,
+{
+    fn should_unpack_parent(&self, parent_envelope: &T::Envelope) -> bool {
+        // Any child envelope is contained within its parent's envelope
+        parent_envelope.contains_envelope(&self.envelope)
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        leaf.envelope().contains_envelope(&self.envelope)
+    }
+}
+
+/// A [`SelectionFunction`] that selects all [`Temporal`] elements contained within an
+/// envelope that are valid at a given time.
+pub struct SelectInEnvelopeAtFunction<P>
+where
+    P: Point,
+{
+    envelope: AABB<P>,
+    time: P::Scalar,
+}
+
+impl<P> SelectInEnvelopeAtFunction<P>
+where
+    P: Point,
+{
+    pub fn new(envelope: AABB<P>, time: P::Scalar) -> Self {
+        SelectInEnvelopeAtFunction { envelope, time }
+    }
+}
+
+impl<T, P, S> SelectionFunction<Temporal<T, S>> for SelectInEnvelopeAtFunction<P>
+where
+    S: RTreeNum,
+    P: Point<Scalar = S>,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &AABB<TemporalPoint<P>>) -> bool {
+        envelope.lower().time() <= self.time
+            && self.time <= envelope.upper().time()
+            && self.envelope.intersects(&spatial_envelope(envelope))
+    }
+
+    fn should_unpack_leaf(&self, leaf: &Temporal<T, S>) -> bool {
+        leaf.is_valid_at(self.time) && self.envelope.contains_envelope(&leaf.object().envelope())
+    }
+}
+
+/// A [`SelectionFunction`] that selects all [`Temporal`] elements valid at a given time.
+pub struct SelectValidAtFunction<S> {
+    time: S,
+}
+
+impl<S> SelectValidAtFunction<S> {
+    pub fn new(time: S) -> Self {
+        SelectValidAtFunction { time }
+    }
+}
+
+impl<T, P, S> SelectionFunction<Temporal<T, S>> for SelectValidAtFunction<S>
+where
+    S: RTreeNum,
+    P: Point<Scalar = S>,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &AABB<TemporalPoint<P>>) -> bool {
+        envelope.lower().time() <= self.time && self.time <= envelope.upper().time()
+    }
+
+    fn should_unpack_leaf(&self, leaf: &Temporal<T, S>) -> bool {
+        leaf.is_valid_at(self.time)
+    }
+}
+
+/// A [`SelectionFunction`] that selects all [`Moving`] elements intersecting an envelope
+/// at a given time.
+pub struct SelectMovingEnvelopeIntersectingFunction<P>
+where
+    P: Point,
+{
+    envelope: AABB<P>,
+    time: P::Scalar,
+}
+
+impl<P> SelectMovingEnvelopeIntersectingFunction<P>
+where
+    P: Point,
+{
+    pub fn new(envelope: AABB<P>, time: P::Scalar) -> Self {
+        SelectMovingEnvelopeIntersectingFunction { envelope, time }
+    }
+}
+
+impl<T, P> SelectionFunction<Moving<T, P>> for SelectMovingEnvelopeIntersectingFunction<P>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &MovingAABB<P>) -> bool {
+        envelope.at_time(self.time).intersects(&self.envelope)
+    }
+
+    fn should_unpack_leaf(&self, leaf: &Moving<T, P>) -> bool {
+        leaf.envelope_at(self.time).intersects(&self.envelope)
+    }
+}
+
+/// A [`SelectionFunction`] that selects all elements within a longitude / latitude box
+/// that may cross the antimeridian.
+pub struct SelectInGeographicEnvelopeFunction<P>
+where
+    P: Point,
+{
+    east: AABB<P>,
+    west: AABB<P>,
+}
+
+impl<P> SelectInGeographicEnvelopeFunction<P>
+where
+    P: Point,
+    P::Scalar: Float,
+{
+    pub fn new(lower: P, upper: P) -> Self {
+        let (east, west) = AABB::split_at_antimeridian(lower, upper);
+        SelectInGeographicEnvelopeFunction { east, west }
+    }
+}
+
+impl<T, P> SelectionFunction<T> for SelectInGeographicEnvelopeFunction<P>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &AABB<P>) -> bool {
+        self.east.intersects(envelope) || self.west.intersects(envelope)
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        let envelope = leaf.envelope();
+        self.east.contains_envelope(&envelope) || self.west.contains_envelope(&envelope)
+    }
+}
+
+/// A [`SelectionFunction`] that selects all elements intersecting a longitude / latitude
+/// box that may cross the antimeridian.
+pub struct SelectInGeographicEnvelopeFuncIntersecting<P>
+where
+    P: Point,
+{
+    east: AABB<P>,
+    west: AABB<P>,
+}
+
+impl<P> SelectInGeographicEnvelopeFuncIntersecting<P>
+where
+    P: Point,
+    P::Scalar: Float,
+{
+    pub fn new(lower: P, upper: P) -> Self {
+        let (east, west) = AABB::split_at_antimeridian(lower, upper);
+        SelectInGeographicEnvelopeFuncIntersecting { east, west }
+    }
+}
+
+impl<T, P> SelectionFunction<T> for SelectInGeographicEnvelopeFuncIntersecting<P>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &AABB<P>) -> bool {
+        self.east.intersects(envelope) || self.west.intersects(envelope)
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        let envelope = leaf.envelope();
+        self.east.intersects(&envelope) || self.west.intersects(&envelope)
+    }
+}
+
+/// A [`SelectionFunction`] that selects all elements intersecting an envelope within a
+/// periodic domain.
+///
+/// Each axis is checked separately, which is equivalent to checking all periodic images of
+/// the query envelope.
+pub struct SelectPeriodicEnvelopeIntersectingFunction<P>
+where
+    P: Point,
+{
+    envelope: AABB<P>,
+    extent: P,
+}
+
+impl<P> SelectPeriodicEnvelopeIntersectingFunction<P>
+where
+    P: Point,
+{
+    pub fn new(envelope: AABB<P>, extent: P) -> Self {
+        SelectPeriodicEnvelopeIntersectingFunction { envelope, extent }
+    }
+
+    fn intersects(&self, other: &AABB<P>) -> bool {
+        (0..P::DIMENSIONS).all(|i| {
+            let extent = self.extent.nth(i);
+            let (lower, upper) = (self.envelope.lower().nth(i), self.envelope.upper().nth(i));
+            let (other_lower, other_upper) = (other.lower().nth(i), other.upper().nth(i));
+            // Two intervals intersect if either one contains an image of the other's start
+            rem_euclid(other_lower - lower, extent) <= upper - lower
+                || rem_euclid(lower - other_lower, extent) <= other_upper - other_lower
+        })
+    }
+}
+
+impl<T, P> SelectionFunction<T> for SelectPeriodicEnvelopeIntersectingFunction<P>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &AABB<P>) -> bool {
+        self.intersects(envelope)
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        self.intersects(&leaf.envelope())
+    }
+}
+
+/// A [`SelectionFunction`] that selects all elements whose envelope intersects a line segment.
+pub struct SelectSegmentIntersectingFunction<T>
+where
+    T: RTreeObject,
+{
+    segment: Line<<T::Envelope as Envelope>::Point>,
+}
+
+impl<T> SelectSegmentIntersectingFunction<T>
+where
+    T: RTreeObject,
+{
+    pub fn new(segment: Line<<T::Envelope as Envelope>::Point>) -> Self {
+        SelectSegmentIntersectingFunction { segment }
+    }
+}
+
+impl<T, P> SelectionFunction<T> for SelectSegmentIntersectingFunction<T>
+where
+    P: Point,
+    P::Scalar: Float,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &AABB<P>) -> bool {
+        self.segment.intersects_aabb(envelope)
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        self.segment.intersects_aabb(&leaf.envelope())
+    }
+}
+
+/// A [`SelectionFunction`] that selects all elements within a convex polytope.
+///
+/// The polytope is defined as the intersection of a set of [`HalfSpace`]s, e.g. the six
+/// clipping planes of a view frustum. An element is selected unless its envelope lies
+/// completely outside of one of the half-spaces. Just like common frustum culling
+/// techniques, this test is conservative: elements close to the polytope's corners may be
+/// selected even if they lie outside.
+///
+/// Parent nodes lying fully inside the polytope are returned as a whole without
+/// checking their elements individually.
+///
+/// # Example
+/// ```
+/// use rstar::{RTree, SelectInPolytopeFunction};
+/// use rstar::primitives::HalfSpace;
+///
+/// // The triangle x >= 0, y >= 0, x + y <= 2
+/// let triangle = SelectInPolytopeFunction::new(vec![
+///     HalfSpace::new([-1.0, 0.0], 0.0),
+///     HalfSpace::new([0.0, -1.0], 0.0),
+///     HalfSpace::new([1.0, 1.0], 2.0),
+/// ]);
+///
+/// let tree = RTree::bulk_load(vec![[0.5, 0.5], [1.5, 1.5], [-1.0, 0.0]]);
+/// let selected: Vec<_> = tree.locate_with_selection_function(triangle).collect();
+/// assert_eq!(selected, vec![&[0.5, 0.5]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SelectInPolytopeFunction<P>
+where
+    P: Point,
+{
+    half_spaces: Vec<HalfSpace<P>>,
+}
+
+impl<P> SelectInPolytopeFunction<P>
+where
+    P: Point,
+{
+    /// Creates a new selection function selecting the intersection of all given half-spaces.
+    pub fn new(half_spaces: Vec<HalfSpace<P>>) -> Self {
+        SelectInPolytopeFunction { half_spaces }
+    }
+
+    /// Returns the half-spaces defining this polytope.
+    pub fn half_spaces(&self) -> &[HalfSpace<P>] {
+        &self.half_spaces
+    }
+
+    fn classify(&self, aabb: &AABB<P>) -> ParentSelection {
+        let mut result = ParentSelection::SelectAll;
+        for half_space in &self.half_spaces {
+            let (min, max) = half_space.projection_bounds(aabb);
+            if min > half_space.offset {
+                return ParentSelection::Skip;
+            }
+            if max > half_space.offset {
+                result = ParentSelection::Unpack;
+            }
+        }
+        result
+    }
+}
+
+impl<T, P> SelectionFunction<T> for SelectInPolytopeFunction<P>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &AABB<P>) -> bool {
+        self.classify(envelope) != ParentSelection::Skip
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        self.classify(&leaf.envelope()) != ParentSelection::Skip
+    }
+
+    fn select_parent(&self, envelope: &AABB<P>) -> ParentSelection {
+        self.classify(envelope)
+    }
+}
+
+/// A [`SelectionFunction`] that selects all elements within a simple polygon.
+///
+/// The polygon is given by its vertices, the last vertex is implicitly connected to the
+/// first one. An element is selected if the center of its envelope lies within the
+/// polygon, as determined by [`contains_point`](Self::contains_point). Parent nodes lying
+/// fully outside of the polygon are pruned, parent nodes lying fully inside are returned as
+/// a whole without checking their elements individually.
+///
+/// This selection function is only available for two-dimensional trees and requires a
+/// floating point [`Point`] type.
+///
+/// # Example
+/// ```
+/// use rstar::{RTree, SelectInPolygonFunction};
+///
+/// // An L-shaped lasso
+/// let lasso = SelectInPolygonFunction::new(vec![
+///     [0.0, 0.0],
+///     [2.0, 0.0],
+///     [2.0, 1.0],
+///     [1.0, 1.0],
+///     [1.0, 2.0],
+///     [0.0, 2.0],
+/// ]);
+///
+/// let tree = RTree::bulk_load(vec![[0.5, 1.5], [1.5, 1.5], [1.5, 0.5]]);
+/// let mut selected: Vec<_> = tree.locate_with_selection_function(lasso).collect();
+/// selected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+/// assert_eq!(selected, vec![&[0.5, 1.5], &[1.5, 0.5]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SelectInPolygonFunction<P>
+where
+    P: Point,
+{
+    vertices: Vec<P>,
+}
+
+impl<P> SelectInPolygonFunction<P>
+where
+    P: Point,
+    P::Scalar: Float,
+{
+    /// Creates a new selection function selecting the polygon with the given vertices.
+    ///
+    /// Fails to compile if `P` is not a two-dimensional point type.
+    pub fn new(vertices: Vec<P>) -> Self {
+        const {
+            assert!(
+                P::DIMENSIONS == 2,
+                "SelectInPolygonFunction requires two-dimensional points"
+            )
+        };
+        SelectInPolygonFunction { vertices }
+    }
+
+    /// Returns the polygon's vertices.
+    pub fn vertices(&self) -> &[P] {
+        &self.vertices
+    }
+
+    /// Returns `true` if a point lies within the polygon.
+    ///
+    /// Uses the even-odd rule. The result for points lying exactly on the polygon's
+    /// boundary is unspecified.
+    pub fn contains_point(&self, point: &P) -> bool {
+        let (x, y) = (point.nth(0), point.nth(1));
+        let mut result = false;
+        for edge in self.edges() {
+            let (x1, y1) = (edge.from.nth(0), edge.from.nth(1));
+            let (x2, y2) = (edge.to.nth(0), edge.to.nth(1));
+            if (y1 > y) != (y2 > y) && x < x1 + (y - y1) * (x2 - x1) / (y2 - y1) {
+                result = !result;
+            }
+        }
+        result
+    }
+
+    fn edges(&self) -> impl Iterator<Item = Line<P>> + '_ {
+        let next_vertices = self.vertices.iter().cycle().skip(1);
+        self.vertices
+            .iter()
+            .zip(next_vertices)
+            .map(|(from, to)| Line::new(from.clone(), to.clone()))
+    }
+
+    fn classify(&self, aabb: &AABB<P>) -> ParentSelection {
+        if self.edges().any(|edge| edge.intersects_aabb(aabb)) {
+            return ParentSelection::Unpack;
+        }
+        // No edge crosses the AABB, so it lies either fully inside or fully outside
+        if self.contains_point(&aabb.lower()) {
+            ParentSelection::SelectAll
+        } else {
+            ParentSelection::Skip
+        }
+    }
+}
+
+impl<T, P> SelectionFunction<T> for SelectInPolygonFunction<P>
+where
+    P: Point,
+    P::Scalar: Float,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &AABB<P>) -> bool {
+        self.classify(envelope) != ParentSelection::Skip
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        self.contains_point(&leaf.envelope().center())
+    }
+
+    fn select_parent(&self, envelope: &AABB<P>) -> ParentSelection {
+        self.classify(envelope)
+    }
+}
+
 pub struct SelectAllFunc;
 
 impl<T> SelectionFunction<T> for SelectAllFunc
@@ -105,6 +667,31 @@ where
     }
 }
 
+/// A [`SelectionFunction`] that selects all elements matching a predicate.
+pub struct SelectByPredicateFunction<F> {
+    predicate: F,
+}
+
+impl<F> SelectByPredicateFunction<F> {
+    pub fn new(predicate: F) -> Self {
+        SelectByPredicateFunction { predicate }
+    }
+}
+
+impl<T, F> SelectionFunction<T> for SelectByPredicateFunction<F>
+where
+    T: RTreeObject,
+    F: Fn(&T) -> bool,
+{
+    fn should_unpack_parent(&self, _: &T::Envelope) -> bool {
+        true
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        (self.predicate)(leaf)
+    }
+}
+
 /// A [trait.SelectionFunction] that only selects elements whose envelope
 /// contains a specific point.
 pub struct SelectAtPointFunction<T>
@@ -206,6 +793,139 @@ where
     }
 }
 
+/// A [`SelectionFunction`] that selects all point objects within a distance measured by a
+/// [`Metric`].
+pub struct SelectWithinMetricDistanceFunction<P, M>
+where
+    P: Point,
+{
+    query_point: P,
+    max_distance: P::Scalar,
+    metric: M,
+}
+
+impl<P, M> SelectWithinMetricDistanceFunction<P, M>
+where
+    P: Point,
+    M: Metric<P>,
+{
+    pub fn new(query_point: P, max_distance: P::Scalar, metric: M) -> Self {
+        SelectWithinMetricDistanceFunction {
+            query_point,
+            max_distance,
+            metric,
+        }
+    }
+}
+
+impl<T, P, M> SelectionFunction<T> for SelectWithinMetricDistanceFunction<P, M>
+where
+    P: Point,
+    M: Metric<P>,
+    T: PointObject + RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &AABB<P>) -> bool {
+        self.metric.aabb_distance(envelope, &self.query_point) <= self.max_distance
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        self.metric.distance(&leaf.position(), &self.query_point) <= self.max_distance
+    }
+}
+
+/// A [`SelectionFunction`] that selects all elements whose envelope intersects a ball.
+pub struct SelectIntersectingBallFunction<T>
+where
+    T: RTreeObject,
+{
+    center: <T::Envelope as Envelope>::Point,
+    squared_radius: Distance<T>,
+}
+
+impl<T> SelectIntersectingBallFunction<T>
+where
+    T: RTreeObject,
+{
+    pub fn new(center: <T::Envelope as Envelope>::Point, squared_radius: Distance<T>) -> Self {
+        SelectIntersectingBallFunction {
+            center,
+            squared_radius,
+        }
+    }
+}
+
+impl<T, P> SelectionFunction<T> for SelectIntersectingBallFunction<T>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &T::Envelope) -> bool {
+        envelope.distance_2(&self.center) <= self.squared_radius
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        self.should_unpack_parent(&leaf.envelope())
+    }
+
+    fn select_parent(&self, envelope: &T::Envelope) -> ParentSelection {
+        select_in_ball(envelope, &self.center, self.squared_radius)
+    }
+}
+
+/// A [`SelectionFunction`] that selects all elements whose envelope lies fully within a ball.
+pub struct SelectInBallFunction<T>
+where
+    T: RTreeObject,
+{
+    center: <T::Envelope as Envelope>::Point,
+    squared_radius: Distance<T>,
+}
+
+impl<T> SelectInBallFunction<T>
+where
+    T: RTreeObject,
+{
+    pub fn new(center: <T::Envelope as Envelope>::Point, squared_radius: Distance<T>) -> Self {
+        SelectInBallFunction {
+            center,
+            squared_radius,
+        }
+    }
+}
+
+impl<T, P> SelectionFunction<T> for SelectInBallFunction<T>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn should_unpack_parent(&self, envelope: &T::Envelope) -> bool {
+        envelope.distance_2(&self.center) <= self.squared_radius
+    }
+
+    fn should_unpack_leaf(&self, leaf: &T) -> bool {
+        leaf.envelope().max_distance_2(&self.center) <= self.squared_radius
+    }
+
+    fn select_parent(&self, envelope: &T::Envelope) -> ParentSelection {
+        select_in_ball(envelope, &self.center, self.squared_radius)
+    }
+}
+
+/// Skips envelopes outside of a ball and selects envelopes lying fully within it.
+fn select_in_ball<P: Point>(
+    envelope: &AABB<P>,
+    center: &P,
+    squared_radius: P::Scalar,
+) -> ParentSelection {
+    if envelope.distance_2(center) > squared_radius {
+        ParentSelection::Skip
+    } else if envelope.max_distance_2(center) <= squared_radius {
+        ParentSelection::SelectAll
+    } else {
+        ParentSelection::Unpack
+    }
+}
+
 pub struct SelectByAddressFunction<T>
 where
     T: RTreeObject,
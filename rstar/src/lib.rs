@@ -29,6 +29,12 @@
 //! documentation on the [mint] module for an expample of an
 //! integration with the
 //! [`nalgebra`](https://crates.io/crates/nalgebra) crate.
+//!
+//! # Parallel queries
+//! Enable the `rayon` feature for parallel iterators and batch queries using
+//! [rayon](https://crates.io/crates/rayon), e.g.
+//! [`RTree::par_iter`](RTree#method.par_iter) or
+//! [`RTree::par_nearest_neighbors_batch`](RTree#method.par_nearest_neighbors_batch).
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 #![cfg_attr(not(test), no_std)]
@@ -38,6 +44,7 @@ extern crate alloc;
 mod aabb;
 mod algorithm;
 mod envelope;
+pub mod metric;
 mod node;
 mod object;
 mod params;
@@ -52,11 +59,15 @@ pub mod mint;
 mod test_utilities;
 
 pub use crate::aabb::AABB;
+pub use crate::algorithm::best_first::BestFirstFunction;
+pub use crate::algorithm::nearest_neighbor::NearestNeighborQuery;
 pub use crate::algorithm::rstar::RStarInsertionStrategy;
-pub use crate::algorithm::selection_functions::SelectionFunction;
+pub use crate::algorithm::selection_functions::{
+    ParentSelection, SelectInPolygonFunction, SelectInPolytopeFunction, SelectionFunction,
+};
 pub use crate::envelope::Envelope;
 pub use crate::node::{ParentNode, RTreeNode};
-pub use crate::object::{PointDistance, RTreeObject};
+pub use crate::object::{GeometryDistance, PointDistance, PointObject, RTreeObject};
 pub use crate::params::{DefaultParams, InsertionStrategy, RTreeParams};
 pub use crate::point::{Point, RTreeNum};
 pub use crate::rtree::RTree;
@@ -0,0 +1,636 @@
+//! Contains distance metrics for nearest neighbor and within-distance queries.
+//!
+//! Most queries of an r-tree use the squared euclidean distance provided by
+//! [PointDistance](crate::PointDistance). The [Metric] trait allows to use other
+//! distance functions instead:
+//!  - [Manhattan] for the L1 distance, e.g. for movement on a grid.
+//!  - [Chebyshev] for the L∞ distance, e.g. for a king's movement on a chessboard.
+//!  - [WeightedEuclidean] for a euclidean distance with a weight for each axis.
+//!  - [Haversine] for the great-circle distance between longitude / latitude points.
+//!  - [Periodic] for the euclidean distance within a periodic domain.
+//!
+//! A metric is used by wrapping a query point into a [MetricQuery], which can be passed
+//! to [RTree::nearest_neighbor_to](crate::RTree::nearest_neighbor_to) and the other
+//! `_to` methods, or by calling
+//! [RTree::locate_within_metric_distance](crate::RTree::locate_within_metric_distance).
+//!
+//! # Example
+//! ```
+//! use rstar::RTree;
+//! use rstar::metric::{Chebyshev, Manhattan, MetricQuery};
+//!
+//! let tree = RTree::bulk_load(vec![[3.0, 0.0], [2.0, 2.0]]);
+//!
+//! let query = MetricQuery::new([0.0, 0.0], Manhattan);
+//! assert_eq!(tree.nearest_neighbor_to(query), Some(&[3.0, 0.0]));
+//!
+//! let query = MetricQuery::new([0.0, 0.0], Chebyshev);
+//! assert_eq!(tree.nearest_neighbor_to(query), Some(&[2.0, 2.0]));
+//! assert_eq!(tree.locate_within_metric_distance([0.0, 0.0], 2.0, Chebyshev).count(), 1);
+//! ```
+use crate::algorithm::nearest_neighbor::NearestNeighborQuery;
+use crate::object::Distance;
+use crate::point::{max_inline, min_inline, Point, PointExt, RTreeNum};
+use crate::{RTreeObject, AABB};
+
+use num_traits::{Float, NumCast, One, Signed, Zero};
+
+/// Defines a distance function between points.
+///
+/// The values returned by a metric are used for comparisons only. They do not need to
+/// be actual distances: [WeightedEuclidean], for example, returns _squared_ distances,
+/// just like [PointDistance::distance_2](crate::PointDistance::distance_2).
+///
+/// # Notes
+/// [aabb_distance](Self::aabb_distance) **must** never be larger than the distance to
+/// any point contained within the AABB. Otherwise, queries may miss objects.
+pub trait Metric<P>
+where
+    P: Point,
+{
+    /// Returns the distance between two points.
+    fn distance(&self, from: &P, to: &P) -> P::Scalar;
+
+    /// Returns the distance between an AABB and a point.
+    ///
+    /// The provided default implementation returns the distance to the AABB's
+    /// [min_point](AABB::min_point). This is correct for all metrics that grow with the
+    /// absolute difference along each axis, which is the case for all metrics in this
+    /// module.
+    fn aabb_distance(&self, aabb: &AABB<P>, point: &P) -> P::Scalar {
+        self.distance(&aabb.min_point(point), point)
+    }
+}
+
+/// The Manhattan or L1 distance, summing the absolute differences along all axes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Manhattan;
+
+impl<P> Metric<P> for Manhattan
+where
+    P: Point,
+{
+    fn distance(&self, from: &P, to: &P) -> P::Scalar {
+        from.sub(to)
+            .fold(Zero::zero(), |acc, value| acc + value.abs())
+    }
+}
+
+/// The Chebyshev or L∞ distance, taking the largest absolute difference along any axis.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Chebyshev;
+
+impl<P> Metric<P> for Chebyshev
+where
+    P: Point,
+{
+    fn distance(&self, from: &P, to: &P) -> P::Scalar {
+        from.sub(to)
+            .fold(Zero::zero(), |acc, value| max_inline(acc, value.abs()))
+    }
+}
+
+/// The _squared_ euclidean distance with a weight for each axis.
+///
+/// The squared difference along each axis is multiplied by the corresponding
+/// coordinate of `weights` before summing them up. All weights must not be negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeightedEuclidean<P>
+where
+    P: Point,
+{
+    /// The weight of each axis.
+    pub weights: P,
+}
+
+impl<P> WeightedEuclidean<P>
+where
+    P: Point,
+{
+    /// Creates a new weighted euclidean metric.
+    pub fn new(weights: P) -> Self {
+        WeightedEuclidean { weights }
+    }
+}
+
+impl<P> Metric<P> for WeightedEuclidean<P>
+where
+    P: Point,
+{
+    fn distance(&self, from: &P, to: &P) -> P::Scalar {
+        let diff = from.sub(to);
+        diff.component_wise(&diff, |l, r| l * r).dot(&self.weights)
+    }
+}
+
+/// The great-circle distance between longitude / latitude points on a sphere.
+///
+/// Points are expected to store the longitude in degrees within `[-180, 180]` as first
+/// and the latitude in degrees within `[-90, 90]` as second coordinate, e.g. WGS84
+/// coordinates. Distances are calculated with the haversine formula and returned in
+/// the unit of the sphere's radius.
+///
+/// In contrast to the planar distance of [AABB::distance_2], the distance between an
+/// AABB and a point takes both the curvature of the sphere and the antimeridian into
+/// account: a point at a longitude of `179` is considered close to an AABB starting
+/// at `-180`.
+///
+/// # Example
+/// ```
+/// use rstar::RTree;
+/// use rstar::metric::{Haversine, MetricQuery};
+///
+/// let cities = RTree::bulk_load(vec![
+///     [-178.0, -18.0], // Close to Fiji
+///     [174.8, -41.3], // Wellington
+/// ]);
+///
+/// // Planar distances would place Wellington closer to Suva
+/// let suva = [178.4, -18.1];
+/// let query = MetricQuery::new(suva, Haversine::earth());
+/// let (nearest, distance) = cities.nearest_neighbor_to_with_distance_2(query).unwrap();
+/// assert_eq!(nearest, &[-178.0, -18.0]);
+/// assert!((distance - 380_800.0f64).abs() < 500.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Haversine<S> {
+    /// The sphere's radius.
+    pub radius: S,
+}
+
+impl<S> Haversine<S>
+where
+    S: RTreeNum + Float,
+{
+    /// Creates a new haversine metric for a sphere with a given radius.
+    pub fn new(radius: S) -> Self {
+        Haversine { radius }
+    }
+
+    /// Creates a new haversine metric using the earth's mean radius in meters.
+    pub fn earth() -> Self {
+        Self::new(NumCast::from(6_371_008.8).unwrap())
+    }
+
+    /// Converts a haversine value into a distance.
+    fn distance_from_haversine(&self, haversine: S) -> S {
+        let two = S::one() + S::one();
+        two * self.radius * haversine.sqrt().min(S::one()).asin()
+    }
+}
+
+/// Returns the haversine of an angle given in degrees.
+fn haversine<S: Float>(angle: S) -> S {
+    let two = S::one() + S::one();
+    (angle.to_radians() / two).sin().powi(2)
+}
+
+/// Returns the haversine of the central angle between two points, given the haversine
+/// of their longitude difference.
+fn haversine_distance<S: Float>(haversine_longitude: S, latitude1: S, latitude2: S) -> S {
+    latitude1.to_radians().cos() * latitude2.to_radians().cos() * haversine_longitude
+        + haversine(latitude1 - latitude2)
+}
+
+impl<P> Metric<P> for Haversine<P::Scalar>
+where
+    P: Point,
+    P::Scalar: Float,
+{
+    fn distance(&self, from: &P, to: &P) -> P::Scalar {
+        let haversine_longitude = haversine(from.nth(0) - to.nth(0));
+        self.distance_from_haversine(haversine_distance(
+            haversine_longitude,
+            from.nth(1),
+            to.nth(1),
+        ))
+    }
+
+    fn aabb_distance(&self, aabb: &AABB<P>, point: &P) -> P::Scalar {
+        let (longitude, latitude) = (point.nth(0), point.nth(1));
+        let (min_longitude, min_latitude) = (aabb.lower().nth(0), aabb.lower().nth(1));
+        let (max_longitude, max_latitude) = (aabb.upper().nth(0), aabb.upper().nth(1));
+
+        if min_longitude <= longitude && longitude <= max_longitude {
+            // The closest point lies on the same meridian
+            let difference = if latitude < min_latitude {
+                min_latitude - latitude
+            } else if latitude > max_latitude {
+                latitude - max_latitude
+            } else {
+                Zero::zero()
+            };
+            return self.distance_from_haversine(haversine(difference));
+        }
+
+        // The closest point lies on the closer of both bounding meridians. The haversine
+        // is periodic, which takes care of the antimeridian.
+        let haversine_longitude =
+            haversine(longitude - min_longitude).min(haversine(longitude - max_longitude));
+        // The latitude at which the great circle through the point is closest to that
+        // meridian
+        let cos_longitude = P::Scalar::one() - (haversine_longitude + haversine_longitude);
+        let extremum_latitude = if cos_longitude <= Zero::zero() {
+            let right_angle: P::Scalar = NumCast::from(90.0).unwrap();
+            if latitude > Zero::zero() {
+                right_angle
+            } else {
+                -right_angle
+            }
+        } else {
+            (latitude.to_radians().tan() / cos_longitude)
+                .atan()
+                .to_degrees()
+        };
+
+        let result =
+            if min_latitude < extremum_latitude && extremum_latitude < max_latitude {
+                haversine_distance(haversine_longitude, latitude, extremum_latitude)
+            } else {
+                haversine_distance(haversine_longitude, latitude, min_latitude).min(
+                    haversine_distance(haversine_longitude, latitude, max_latitude),
+                )
+            };
+        self.distance_from_haversine(result)
+    }
+}
+
+/// The _squared_ euclidean distance within a periodic (toroidal) domain.
+///
+/// Along each axis, the domain wraps around after the corresponding coordinate of
+/// `extent`, e.g. a point at `x = 0` lies next to a point at `x = extent`. Distances are
+/// measured between the closest periodic images of two points (minimum image convention).
+/// All extents must be positive.
+///
+/// See also [RTree::nearest_neighbor_iter_with_distance_2_periodic](crate::RTree::nearest_neighbor_iter_with_distance_2_periodic)
+/// and [RTree::locate_within_distance_periodic](crate::RTree::locate_within_distance_periodic).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Periodic<P>
+where
+    P: Point,
+{
+    /// The domain's extent along each axis.
+    pub extent: P,
+}
+
+impl<P> Periodic<P>
+where
+    P: Point,
+{
+    /// Creates a new periodic metric for a domain with a given extent.
+    pub fn new(extent: P) -> Self {
+        Periodic { extent }
+    }
+}
+
+/// Returns the non-negative remainder of `value / extent`.
+pub(crate) fn rem_euclid<S: RTreeNum>(value: S, extent: S) -> S {
+    let result = value % extent;
+    if result < Zero::zero() {
+        result + extent
+    } else {
+        result
+    }
+}
+
+impl<P> Metric<P> for Periodic<P>
+where
+    P: Point,
+{
+    fn distance(&self, from: &P, to: &P) -> P::Scalar {
+        let mut result = P::Scalar::zero();
+        for i in 0..P::DIMENSIONS {
+            let extent = self.extent.nth(i);
+            let (from, to) = (from.nth(i), to.nth(i));
+            let diff = min_inline(rem_euclid(from - to, extent), rem_euclid(to - from, extent));
+            result = result + diff * diff;
+        }
+        result
+    }
+
+    fn aabb_distance(&self, aabb: &AABB<P>, point: &P) -> P::Scalar {
+        let mut result = P::Scalar::zero();
+        for i in 0..P::DIMENSIONS {
+            let extent = self.extent.nth(i);
+            let (lower, upper) = (aabb.lower().nth(i), aabb.upper().nth(i));
+            let x = point.nth(i);
+            if rem_euclid(x - lower, extent) <= upper - lower {
+                // Some periodic image of x lies within the AABB
+                continue;
+            }
+            let diff = min_inline(rem_euclid(lower - x, extent), rem_euclid(x - upper, extent));
+            result = result + diff * diff;
+        }
+        result
+    }
+}
+
+/// A query point combined with a [Metric].
+///
+/// Implements [NearestNeighborQuery] for all objects with an [AABB] envelope and can be
+/// passed to [RTree::nearest_neighbor_to](crate::RTree::nearest_neighbor_to) and the
+/// other `_to` methods. All returned distances are measured with the given metric.
+///
+/// The distance to an object is the distance to its envelope. This is exact for points
+/// and [Rectangle](crate::primitives::Rectangle)s, other objects should be wrapped into
+/// a custom [NearestNeighborQuery] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MetricQuery<P, M> {
+    /// The query point.
+    pub point: P,
+    /// The metric used to measure distances.
+    pub metric: M,
+}
+
+impl<P, M> MetricQuery<P, M>
+where
+    P: Point,
+    M: Metric<P>,
+{
+    /// Creates a new query for a point and a metric.
+    pub fn new(point: P, metric: M) -> Self {
+        MetricQuery { point, metric }
+    }
+}
+
+impl<P, M, T> NearestNeighborQuery<T> for MetricQuery<P, M>
+where
+    P: Point,
+    M: Metric<P>,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn envelope_distance_2(&self, envelope: &AABB<P>) -> Distance<T> {
+        self.metric.aabb_distance(envelope, &self.point)
+    }
+
+    fn object_distance_2(&self, object: &T) -> Distance<T> {
+        self.metric.aabb_distance(&object.envelope(), &self.point)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Chebyshev, Haversine, Manhattan, Metric, MetricQuery, Periodic, WeightedEuclidean,
+    };
+    use crate::object::PointDistance;
+    use crate::primitives::Rectangle;
+    use crate::test_utilities::*;
+    use crate::{RTree, RTreeObject, AABB};
+
+    #[test]
+    fn test_metric_distances() {
+        let (a, b) = ([1.0, 2.0], [-2.0, 4.0]);
+        assert_eq!(Manhattan.distance(&a, &b), 5.0);
+        assert_eq!(Chebyshev.distance(&a, &b), 3.0);
+        assert_eq!(WeightedEuclidean::new([1.0, 1.0]).distance(&a, &b), 13.0);
+        assert_eq!(WeightedEuclidean::new([2.0, 0.5]).distance(&a, &b), 20.0);
+
+        let aabb = AABB::from_corners([0.0, 0.0], [1.0, 1.0]);
+        assert_eq!(Manhattan.aabb_distance(&aabb, &[3.0, -1.0]), 3.0);
+        assert_eq!(Chebyshev.aabb_distance(&aabb, &[3.0, -1.0]), 2.0);
+        assert_eq!(Chebyshev.aabb_distance(&aabb, &[0.5, 0.5]), 0.0);
+        assert_eq!(
+            WeightedEuclidean::new([1.0, 4.0]).aabb_distance(&aabb, &[3.0, -1.0]),
+            8.0
+        );
+    }
+
+    fn check_metric<M: Metric<[f64; 2]> + Copy>(metric: M) {
+        let points = create_random_points(500, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+
+        for query_point in create_random_points(20, SEED_2) {
+            let mut expected: Vec<_> = points
+                .iter()
+                .map(|p| metric.distance(p, &query_point))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let query = MetricQuery::new(query_point, metric);
+            let distances: Vec<_> = tree
+                .nearest_neighbor_iter_to_with_distance_2(query)
+                .map(|(_, distance)| distance)
+                .collect();
+            assert_eq!(distances, expected);
+            let (_, distance) = tree.nearest_neighbor_to_with_distance_2(query).unwrap();
+            assert_eq!(distance, expected[0]);
+
+            let max_distance = expected[30];
+            let within: Vec<_> = tree
+                .locate_within_metric_distance(query_point, max_distance, metric)
+                .collect();
+            for point in &within {
+                assert!(metric.distance(point, &query_point) <= max_distance);
+            }
+            let expected_count = expected.iter().filter(|d| **d <= max_distance).count();
+            assert_eq!(within.len(), expected_count);
+        }
+    }
+
+    #[test]
+    fn test_manhattan() {
+        check_metric(Manhattan);
+    }
+
+    #[test]
+    fn test_chebyshev() {
+        check_metric(Chebyshev);
+    }
+
+    #[test]
+    fn test_weighted_euclidean() {
+        check_metric(WeightedEuclidean::new([1.0, 1.0]));
+        check_metric(WeightedEuclidean::new([0.2, 3.0]));
+
+        let point = [0.3, 0.4];
+        let other = [0.6, 0.1];
+        assert_eq!(
+            WeightedEuclidean::new([1.0, 1.0]).distance(&point, &other),
+            point.distance_2(&other)
+        );
+    }
+
+    #[test]
+    fn test_metric_with_rectangles() {
+        let rectangles = create_random_rectangles(200, SEED_1);
+        let tree = RTree::bulk_load(rectangles.clone());
+        let query_point = [0.5, 0.5];
+
+        let mut expected: Vec<_> = rectangles
+            .iter()
+            .map(|r: &Rectangle<_>| Manhattan.aabb_distance(&r.envelope(), &query_point))
+            .collect();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let distances: Vec<_> = tree
+            .nearest_neighbor_iter_to_with_distance_2(MetricQuery::new(query_point, Manhattan))
+            .map(|(_, distance)| distance)
+            .collect();
+        assert_eq!(distances, expected);
+    }
+
+    fn create_random_lon_lat_points(num_points: usize, seed: &[u8; 32]) -> Vec<[f64; 2]> {
+        create_random_points(num_points, seed)
+            .into_iter()
+            .map(|[x, y]| [x * 360.0 - 180.0, y * 180.0 - 90.0])
+            .collect()
+    }
+
+    #[test]
+    fn test_haversine_distance() {
+        let earth: Haversine<f64> = Haversine::earth();
+        // A quarter of the equator
+        let quarter = earth.distance(&[0.0, 0.0], &[90.0, 0.0]);
+        assert!((quarter - 10_007_557.2).abs() < 1.0);
+        // Across the antimeridian and both poles
+        assert!((earth.distance(&[179.5, 0.0], &[-179.5, 0.0]) - quarter / 90.0).abs() < 1e-6);
+        assert!(earth.distance(&[10.0, 90.0], &[-170.0, 90.0]) < 1e-6);
+        assert!((earth.distance(&[0.0, 90.0], &[0.0, -90.0]) - quarter * 2.0).abs() < 1e-6);
+
+        let unit = Haversine::new(1.0);
+        let aabb = AABB::from_corners([170.0, -10.0], [180.0, 10.0]);
+        assert_eq!(unit.aabb_distance(&aabb, &[175.0, 0.0]), 0.0);
+        assert!((unit.aabb_distance(&aabb, &[-179.0, 0.0]) - 1.0f64.to_radians()).abs() < 1e-12);
+        assert!((unit.aabb_distance(&aabb, &[175.0, 20.0]) - 10.0f64.to_radians()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_haversine_aabb_lower_bound() {
+        let unit = Haversine::new(1.0);
+        let corners = create_random_lon_lat_points(100, SEED_1);
+        let sizes = create_random_points(100, SEED_2);
+        let query_points = create_random_lon_lat_points(100, SEED_2);
+        for (corner, [width, height]) in corners.iter().zip(sizes) {
+            let upper = [
+                (corner[0] + width * 40.0).min(180.0),
+                (corner[1] + height * 20.0).min(90.0),
+            ];
+            let aabb = AABB::from_corners(*corner, upper);
+            let (lower, upper) = (aabb.lower(), aabb.upper());
+            for query_point in &query_points {
+                let bound = unit.aabb_distance(&aabb, query_point);
+                let mut min_distance = f64::MAX;
+                for i in 0..=50 {
+                    for j in 0..=50 {
+                        let (s, t) = (i as f64 / 50.0, j as f64 / 50.0);
+                        let point = [
+                            lower[0] + (upper[0] - lower[0]) * s,
+                            lower[1] + (upper[1] - lower[1]) * t,
+                        ];
+                        min_distance = min_distance.min(unit.distance(&point, query_point));
+                    }
+                }
+                assert!(bound <= min_distance + 1e-12);
+                // The bound is exact, the sampling only approximates the closest point
+                assert!(min_distance - bound < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn test_haversine_nearest_neighbors() {
+        let earth: Haversine<f64> = Haversine::earth();
+        let points = create_random_lon_lat_points(1000, SEED_1);
+        let tree = RTree::bulk_load(points.clone());
+
+        for query_point in create_random_lon_lat_points(20, SEED_2).into_iter().chain([
+            [180.0, 0.0],
+            [-179.9, 45.0],
+            [0.0, 90.0],
+            [12.0, -89.9],
+        ]) {
+            let mut expected: Vec<_> = points
+                .iter()
+                .map(|p| earth.distance(p, &query_point))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let query = MetricQuery::new(query_point, earth);
+            let distances: Vec<_> = tree
+                .nearest_neighbor_iter_to_with_distance_2(query)
+                .map(|(_, distance)| distance)
+                .take(50)
+                .collect();
+            assert_eq!(distances, expected[..50]);
+
+            let max_distance = expected[20];
+            let within = tree
+                .locate_within_metric_distance(query_point, max_distance, earth)
+                .count();
+            assert_eq!(
+                within,
+                expected.iter().filter(|d| **d <= max_distance).count()
+            );
+        }
+    }
+
+    /// Returns the squared distance between the closest of all shifted images.
+    fn periodic_distance_2_brute_force(from: &[f64; 2], to: &[f64; 2], extent: [f64; 2]) -> f64 {
+        let mut result = f64::MAX;
+        for dx in [-1.0, 0.0, 1.0] {
+            for dy in [-1.0, 0.0, 1.0] {
+                let image = [to[0] + dx * extent[0], to[1] + dy * extent[1]];
+                result = result.min(from.distance_2(&image));
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_periodic_distance() {
+        let periodic = Periodic::new([10.0, 4.0]);
+        assert_eq!(periodic.distance(&[0.5, 1.0], &[9.5, 1.0]), 1.0);
+        assert_eq!(periodic.distance(&[0.5, 0.0], &[9.5, 3.0]), 2.0);
+        assert_eq!(periodic.distance(&[2.0, 2.0], &[3.0, 2.0]), 1.0);
+
+        let aabb = AABB::from_corners([8.0, 1.0], [9.0, 2.0]);
+        assert_eq!(periodic.aabb_distance(&aabb, &[0.5, 1.5]), 1.5 * 1.5);
+        assert_eq!(periodic.aabb_distance(&aabb, &[8.5, 3.5]), 1.5 * 1.5);
+        assert_eq!(periodic.aabb_distance(&aabb, &[18.5, -2.5]), 0.0);
+        assert_eq!(periodic.aabb_distance(&aabb, &[5.0, 1.5]), 9.0);
+    }
+
+    #[test]
+    fn test_periodic_nearest_neighbors() {
+        let extent = [1.0, 2.0];
+        let points: Vec<_> = create_random_points(500, SEED_1)
+            .into_iter()
+            .map(|[x, y]| [x, y * 2.0])
+            .collect();
+        let tree = RTree::bulk_load(points.clone());
+
+        for query_point in create_random_points(20, SEED_2).into_iter().chain([
+            [0.0, 0.0],
+            [0.99, 1.99],
+            [0.0, 1.0],
+        ]) {
+            let mut expected: Vec<_> = points
+                .iter()
+                .map(|p| periodic_distance_2_brute_force(&query_point, p, extent))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let distances: Vec<_> = tree
+                .nearest_neighbor_iter_with_distance_2_periodic(query_point, extent)
+                .map(|(_, distance_2)| distance_2)
+                .collect();
+            assert_eq!(distances.len(), expected.len());
+            for (distance_2, expected_distance_2) in distances.iter().zip(&expected) {
+                assert!((distance_2 - expected_distance_2).abs() < 1e-12);
+            }
+
+            let max_squared_radius = expected[30];
+            let within = tree
+                .locate_within_distance_periodic(query_point, max_squared_radius, extent)
+                .count();
+            let expected_count = points
+                .iter()
+                .filter(|p| Periodic::new(extent).distance(&query_point, p) <= max_squared_radius)
+                .count();
+            assert_eq!(within, expected_count);
+        }
+    }
+}
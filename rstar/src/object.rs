@@ -4,6 +4,7 @@ use alloc::sync::Arc;
 use crate::aabb::AABB;
 use crate::envelope::Envelope;
 use crate::point::{Point, PointExt};
+use crate::primitives::Line;
 
 /// Type alias for distance scalar types derived from `PointDistance` objects
 #[allow(type_alias_bounds)]
@@ -199,6 +200,91 @@ pub trait PointDistance: RTreeObject {
     }
 }
 
+/// Defines the exact distance between an object and a query geometry of type `G`.
+///
+/// [AABB], [Line](crate::primitives::Line) and [Rectangle](crate::primitives::Rectangle)
+/// implement [NearestNeighborQuery](crate::NearestNeighborQuery) for any object implementing
+/// this trait with their type. These queries bound the distance to a node by its envelope and
+/// use this trait to measure the distance to each object.
+///
+/// This trait is implemented for points, [Line](crate::primitives::Line),
+/// [Rectangle](crate::primitives::Rectangle) and the wrappers of this crate.
+///
+/// # Example
+/// ```
+/// use rstar::{GeometryDistance, RTree, RTreeObject, AABB};
+///
+/// struct Sensor {
+///     position: [f64; 2],
+/// }
+///
+/// impl RTreeObject for Sensor {
+///     type Envelope = AABB<[f64; 2]>;
+///
+///     fn envelope(&self) -> Self::Envelope {
+///         AABB::from_point(self.position)
+///     }
+/// }
+///
+/// impl GeometryDistance<AABB<[f64; 2]>> for Sensor {
+///     fn geometry_distance_2(&self, aabb: &AABB<[f64; 2]>) -> f64 {
+///         aabb.distance_2(&self.position)
+///     }
+/// }
+///
+/// let sensors = RTree::bulk_load(vec![
+///     Sensor { position: [0.0, 0.0] },
+///     Sensor { position: [5.0, 5.0] },
+/// ]);
+/// let building = AABB::from_corners([3.0, 4.0], [4.0, 6.0]);
+/// let nearest = sensors.nearest_neighbor_to(building).unwrap();
+/// assert_eq!(nearest.position, [5.0, 5.0]);
+/// ```
+pub trait GeometryDistance<G>: RTreeObject {
+    /// Returns the squared distance between this object and `geometry`.
+    ///
+    /// The result **must** not be smaller than the squared distance between `geometry`
+    /// and this object's envelope.
+    fn geometry_distance_2(&self, geometry: &G) -> Distance<Self>;
+}
+
+/// An object located at a single point.
+///
+/// Some algorithms, like [reverse nearest neighbor queries](crate::RTree::reverse_nearest_neighbors),
+/// measure distances _between_ the objects of a tree. These are only defined for objects
+/// that have a position.
+///
+/// This trait is implemented for all points, and for [GeomWithData](crate::primitives::GeomWithData)
+/// and [CachedEnvelope](crate::primitives::CachedEnvelope) containing one.
+///
+/// # Example
+/// ```
+/// use rstar::{PointObject, RTreeObject, AABB};
+///
+/// struct Customer {
+///     location: [f64; 2],
+///     name: &'static str,
+/// }
+///
+/// impl RTreeObject for Customer {
+///     type Envelope = AABB<[f64; 2]>;
+///
+///     fn envelope(&self) -> Self::Envelope {
+///         AABB::from_point(self.location)
+///     }
+/// }
+///
+/// impl PointObject for Customer {
+///     fn position(&self) -> [f64; 2] {
+///         self.location
+///     }
+/// }
+/// ```
+pub trait PointObject: RTreeObject {
+    /// Returns the point this object is located at.
+    fn position(&self) -> <Self::Envelope as Envelope>::Point;
+}
+
 impl<P> RTreeObject for P
 where
     P: Point,
@@ -236,6 +322,33 @@ where
     }
 }
 
+impl<P> PointObject for P
+where
+    P: Point,
+{
+    fn position(&self) -> P {
+        self.clone()
+    }
+}
+
+impl<P> GeometryDistance<AABB<P>> for P
+where
+    P: Point,
+{
+    fn geometry_distance_2(&self, aabb: &AABB<P>) -> P::Scalar {
+        aabb.distance_2(self)
+    }
+}
+
+impl<P> GeometryDistance<Line<P>> for P
+where
+    P: Point,
+{
+    fn geometry_distance_2(&self, line: &Line<P>) -> P::Scalar {
+        line.distance_2(self)
+    }
+}
+
 impl<T> RTreeObject for Arc<T>
 where
     T: RTreeObject + ?Sized,
@@ -265,6 +378,24 @@ where
     }
 }
 
+impl<T> PointObject for Arc<T>
+where
+    T: PointObject + ?Sized,
+{
+    fn position(&self) -> <Self::Envelope as Envelope>::Point {
+        (**self).position()
+    }
+}
+
+impl<T, G> GeometryDistance<G> for Arc<T>
+where
+    T: GeometryDistance<G> + ?Sized,
+{
+    fn geometry_distance_2(&self, geometry: &G) -> Distance<Self> {
+        (**self).geometry_distance_2(geometry)
+    }
+}
+
 impl<T> RTreeObject for Rc<T>
 where
     T: RTreeObject + ?Sized,
@@ -293,3 +424,21 @@ where
         (**self).distance_2_if_less_or_equal(point, max_distance_2)
     }
 }
+
+impl<T> PointObject for Rc<T>
+where
+    T: PointObject + ?Sized,
+{
+    fn position(&self) -> <Self::Envelope as Envelope>::Point {
+        (**self).position()
+    }
+}
+
+impl<T, G> GeometryDistance<G> for Rc<T>
+where
+    T: GeometryDistance<G> + ?Sized,
+{
+    fn geometry_distance_2(&self, geometry: &G) -> Distance<Self> {
+        (**self).geometry_distance_2(geometry)
+    }
+}
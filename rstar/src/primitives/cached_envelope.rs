@@ -1,5 +1,5 @@
-use crate::object::PointDistance;
 use crate::object::RTreeObject;
+use crate::object::{GeometryDistance, PointDistance, PointObject};
 use crate::{envelope::Envelope, object::Distance};
 use core::ops::Deref;
 
@@ -65,6 +65,26 @@ impl<T: RTreeObject> Deref for CachedEnvelope<T> {
     }
 }
 
+impl<T, G> GeometryDistance<G> for CachedEnvelope<T>
+where
+    T: GeometryDistance<G>,
+    T::Envelope: Clone,
+{
+    fn geometry_distance_2(&self, geometry: &G) -> Distance<Self> {
+        self.inner.geometry_distance_2(geometry)
+    }
+}
+
+impl<T> PointObject for CachedEnvelope<T>
+where
+    T: PointObject,
+    T::Envelope: Clone,
+{
+    fn position(&self) -> <Self::Envelope as Envelope>::Point {
+        self.inner.position()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::CachedEnvelope;
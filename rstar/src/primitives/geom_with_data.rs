@@ -1,5 +1,7 @@
-use crate::object::PointDistance;
+use crate::algorithm::nearest_neighbor::NearestNeighborQuery;
 use crate::object::RTreeObject;
+use crate::object::{GeometryDistance, PointDistance, PointObject};
+use crate::primitives::{Ray, RayIntersect};
 use crate::{envelope::Envelope, object::Distance};
 
 /// An [RTreeObject] with a geometry and some associated data that can be inserted into an r-tree.
@@ -75,6 +77,57 @@ impl<R: RTreeObject, T> GeomWithData<R, T> {
     }
 }
 
+impl<R, T, G> GeometryDistance<G> for GeomWithData<R, T>
+where
+    R: GeometryDistance<G>,
+{
+    fn geometry_distance_2(&self, geometry: &G) -> Distance<Self> {
+        self.geom.geometry_distance_2(geometry)
+    }
+}
+
+impl<R: PointObject, T> PointObject for GeomWithData<R, T> {
+    fn position(&self) -> <Self::Envelope as Envelope>::Point {
+        self.geom.position()
+    }
+}
+
+impl<R: RayIntersect, T> RayIntersect for GeomWithData<R, T> {
+    fn ray_intersection(
+        &self,
+        ray: &Ray<<Self::Envelope as Envelope>::Point>,
+    ) -> Option<Distance<Self>> {
+        self.geom.ray_intersection(ray)
+    }
+}
+
+impl<R, T, U> NearestNeighborQuery<U> for GeomWithData<R, T>
+where
+    R: RTreeObject + NearestNeighborQuery<U>,
+    U: RTreeObject,
+{
+    fn envelope_distance_2(&self, envelope: &U::Envelope) -> Distance<U> {
+        self.geom.envelope_distance_2(envelope)
+    }
+
+    fn object_distance_2(&self, object: &U) -> Distance<U> {
+        self.geom.object_distance_2(object)
+    }
+
+    fn object_distance_2_if_less_or_equal(
+        &self,
+        object: &U,
+        max_distance_2: Distance<U>,
+    ) -> Option<Distance<U>> {
+        self.geom
+            .object_distance_2_if_less_or_equal(object, max_distance_2)
+    }
+
+    fn envelope_min_max_distance_2(&self, envelope: &U::Envelope) -> Distance<U> {
+        self.geom.envelope_min_max_distance_2(envelope)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::GeomWithData;
@@ -0,0 +1,95 @@
+use crate::aabb::AABB;
+use crate::point::{max_inline, min_inline, Point, PointExt};
+use num_traits::Zero;
+
+/// A half-space bounded by a hyperplane, e.g. one of the clipping planes of a view frustum.
+///
+/// The half-space contains all points `x` for which `normal · x <= offset`, i.e. its
+/// normal points _outward_. Half-spaces are used to build
+/// [SelectInPolytopeFunction](crate::SelectInPolytopeFunction).
+///
+/// # Type parameters
+/// `P`: The half-space's [Point] type.
+///
+/// # Example
+/// ```
+/// use rstar::primitives::HalfSpace;
+///
+/// // All points with x <= 2
+/// let half_space = HalfSpace::new([1.0, 0.0], 2.0);
+/// assert!(half_space.contains_point(&[1.0, 5.0]));
+/// assert!(!half_space.contains_point(&[3.0, 0.0]));
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HalfSpace<P>
+where
+    P: Point,
+{
+    /// The outward pointing normal of the bounding hyperplane. Does not need to be normalized.
+    pub normal: P,
+    /// The hyperplane's offset along the normal.
+    pub offset: P::Scalar,
+}
+
+impl<P> HalfSpace<P>
+where
+    P: Point,
+{
+    /// Creates a new half-space containing all points `x` with `normal · x <= offset`.
+    pub fn new(normal: P, offset: P::Scalar) -> Self {
+        HalfSpace { normal, offset }
+    }
+
+    /// Returns true if a point lies within this half-space.
+    pub fn contains_point(&self, point: &P) -> bool {
+        self.normal.dot(point) <= self.offset
+    }
+
+    /// Returns true if an AABB lies fully within this half-space.
+    pub fn contains_aabb(&self, aabb: &AABB<P>) -> bool {
+        self.projection_bounds(aabb).1 <= self.offset
+    }
+
+    /// Returns true if an AABB lies at least partially within this half-space.
+    pub fn intersects_aabb(&self, aabb: &AABB<P>) -> bool {
+        self.projection_bounds(aabb).0 <= self.offset
+    }
+
+    /// Returns the minimum and maximum value of `normal · x` for all `x` within an AABB.
+    pub(crate) fn projection_bounds(&self, aabb: &AABB<P>) -> (P::Scalar, P::Scalar) {
+        let (lower, upper) = (aabb.lower(), aabb.upper());
+        let mut min = P::Scalar::zero();
+        let mut max = P::Scalar::zero();
+        for i in 0..P::DIMENSIONS {
+            let normal = self.normal.nth(i);
+            let (l, u) = (normal * lower.nth(i), normal * upper.nth(i));
+            min = min + min_inline(l, u);
+            max = max + max_inline(l, u);
+        }
+        (min, max)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HalfSpace;
+    use crate::AABB;
+
+    #[test]
+    fn half_space_aabb() {
+        let half_space = HalfSpace::new([1.0, 1.0], 2.0);
+
+        let inside = AABB::from_corners([-1.0, -1.0], [1.0, 1.0]);
+        assert!(half_space.contains_aabb(&inside));
+        assert!(half_space.intersects_aabb(&inside));
+
+        let intersecting = AABB::from_corners([0.0, 0.0], [2.0, 1.0]);
+        assert!(!half_space.contains_aabb(&intersecting));
+        assert!(half_space.intersects_aabb(&intersecting));
+
+        let outside = AABB::from_corners([1.5, 1.0], [3.0, 3.0]);
+        assert!(!half_space.contains_aabb(&outside));
+        assert!(!half_space.intersects_aabb(&outside));
+    }
+}
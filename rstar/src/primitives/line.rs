@@ -1,9 +1,11 @@
+use crate::algorithm::nearest_neighbor::NearestNeighborQuery;
 use crate::envelope::Envelope;
-use crate::object::PointDistance;
 use crate::object::RTreeObject;
-use crate::point::{Point, PointExt};
+use crate::object::{GeometryDistance, PointDistance};
+use crate::point::{max_inline, min_inline, Point, PointExt};
+use crate::primitives::{Ray, RayIntersect};
 use crate::{aabb::AABB, object::Distance};
-use num_traits::{One, Zero};
+use num_traits::{Bounded, Float, One, Zero};
 
 /// A line defined by a start and and end point.
 ///
@@ -101,6 +103,141 @@ where
             p2
         }
     }
+
+    /// Returns the squared distance between the closest points of this line and an [AABB].
+    ///
+    /// Returns zero if the line intersects the AABB.
+    pub(crate) fn distance_2_to_aabb(&self, aabb: &AABB<P>) -> P::Scalar
+    where
+        P::Scalar: Float,
+    {
+        let zero = P::Scalar::zero();
+        let one = P::Scalar::one();
+        let two = one + one;
+        let dir = self.to.sub(&self.from);
+        let (lower, upper) = (aabb.lower(), aabb.upper());
+
+        // The line is split into pieces at every parameter where it enters or
+        // leaves one of the AABB's slabs. Within a piece, the squared distance
+        // is a quadratic function whose minimum can be computed directly.
+        let mut result = <P::Scalar as Bounded>::max_value();
+        let mut start = zero;
+        loop {
+            let mut end = one;
+            for i in 0..P::DIMENSIONS {
+                let d = dir.nth(i);
+                if d != zero {
+                    for bound in [lower.nth(i), upper.nth(i)] {
+                        let t = (bound - self.from.nth(i)) / d;
+                        if start < t && t < end {
+                            end = t;
+                        }
+                    }
+                }
+            }
+
+            let middle = (start + end) / two;
+            let outside_bound = |i: usize| {
+                let coordinate = self.from.nth(i) + dir.nth(i) * middle;
+                if coordinate < lower.nth(i) {
+                    Some(lower.nth(i))
+                } else if coordinate > upper.nth(i) {
+                    Some(upper.nth(i))
+                } else {
+                    None
+                }
+            };
+
+            let (mut offset_dot_dir, mut dir_length_2) = (zero, zero);
+            for i in 0..P::DIMENSIONS {
+                if let Some(bound) = outside_bound(i) {
+                    offset_dot_dir = offset_dot_dir + (self.from.nth(i) - bound) * dir.nth(i);
+                    dir_length_2 = dir_length_2 + dir.nth(i) * dir.nth(i);
+                }
+            }
+            let t = if dir_length_2 > zero {
+                max_inline(min_inline(-offset_dot_dir / dir_length_2, end), start)
+            } else {
+                start
+            };
+
+            let mut distance_2 = zero;
+            for i in 0..P::DIMENSIONS {
+                if let Some(bound) = outside_bound(i) {
+                    let delta = self.from.nth(i) + dir.nth(i) * t - bound;
+                    distance_2 = distance_2 + delta * delta;
+                }
+            }
+            result = min_inline(result, distance_2);
+
+            if end >= one || result == zero {
+                return result;
+            }
+            start = end;
+        }
+    }
+
+    /// Returns true if this line intersects or touches an AABB.
+    pub(crate) fn intersects_aabb(&self, aabb: &AABB<P>) -> bool
+    where
+        P::Scalar: Float,
+    {
+        let ray = Ray::new(self.from.clone(), self.to.sub(&self.from));
+        ray.aabb_intersection(aabb)
+            .is_some_and(|t| t <= P::Scalar::one())
+    }
+
+    /// Returns the squared distance between the closest points of two lines.
+    ///
+    /// Returns zero if the lines intersect.
+    pub(crate) fn distance_2_to_line(&self, other: &Self) -> P::Scalar
+    where
+        P::Scalar: Float,
+    {
+        let zero = P::Scalar::zero();
+        let one = P::Scalar::one();
+        let clamp = |value| max_inline(min_inline(value, one), zero);
+
+        let d1 = self.to.sub(&self.from);
+        let d2 = other.to.sub(&other.from);
+        let r = self.from.sub(&other.from);
+        let a = d1.length_2();
+        let e = d2.length_2();
+        let f = d2.dot(&r);
+
+        // Parameters of the closest points on self (s) and other (t)
+        let (s, t) = if a == zero && e == zero {
+            (zero, zero)
+        } else if a == zero {
+            (zero, clamp(f / e))
+        } else {
+            let c = d1.dot(&r);
+            if e == zero {
+                (clamp(-c / a), zero)
+            } else {
+                let b = d1.dot(&d2);
+                let denominator = a * e - b * b;
+                let s = if denominator != zero {
+                    clamp((b * f - c * e) / denominator)
+                } else {
+                    // Parallel lines, any s will do
+                    zero
+                };
+                let t = (b * s + f) / e;
+                if t < zero {
+                    (clamp(-c / a), zero)
+                } else if t > one {
+                    (clamp((b - c) / a), one)
+                } else {
+                    (s, t)
+                }
+            }
+        };
+
+        let closest_1 = self.from.add(&d1.mul(s));
+        let closest_2 = other.from.add(&d2.mul(t));
+        closest_1.sub(&closest_2).length_2()
+    }
 }
 
 impl<P> PointDistance for Line<P>
@@ -112,9 +249,115 @@ where
     }
 }
 
+impl<P> RayIntersect for Line<P>
+where
+    P: Point,
+    P::Scalar: Float,
+{
+    /// Returns the smallest parameter at which a ray hits this line.
+    ///
+    /// In more than two dimensions, a ray and a line only intersect if they are coplanar.
+    /// Due to rounding errors, such intersections are only reported if the closest points
+    /// of both are exactly equal.
+    fn ray_intersection(&self, ray: &Ray<P>) -> Option<P::Scalar> {
+        let zero = P::Scalar::zero();
+        let one = P::Scalar::one();
+        let d = ray.direction.clone();
+        let e = self.to.sub(&self.from);
+        let a = d.length_2();
+        if a == zero {
+            return None;
+        }
+
+        if P::DIMENSIONS == 2 {
+            let cross = |l: &P, r: &P| l.nth(0) * r.nth(1) - l.nth(1) * r.nth(0);
+            let w = self.from.sub(&ray.origin);
+            let denominator = cross(&d, &e);
+            if denominator != zero {
+                let t = cross(&w, &e) / denominator;
+                let u = cross(&w, &d) / denominator;
+                return (t >= zero && u >= zero && u <= one).then_some(t);
+            }
+            if cross(&w, &d) != zero {
+                // Parallel, but not collinear
+                return None;
+            }
+            // Collinear: the ray hits the line's closer end point or starts on the line
+            let t_from = w.dot(&d) / a;
+            let t_to = self.to.sub(&ray.origin).dot(&d) / a;
+            let (t_min, t_max) = (min_inline(t_from, t_to), max_inline(t_from, t_to));
+            return (t_max >= zero).then_some(max_inline(t_min, zero));
+        }
+
+        // Closest points of the ray (t >= 0) and the line (0 <= u <= 1)
+        let r = ray.origin.sub(&self.from);
+        let c = d.dot(&r);
+        let (t, u) = if e.length_2() == zero {
+            (max_inline(-c / a, zero), zero)
+        } else {
+            let b = d.dot(&e);
+            let f = e.dot(&r);
+            let e_2 = e.length_2();
+            let denominator = a * e_2 - b * b;
+            let t = if denominator != zero {
+                max_inline((b * f - c * e_2) / denominator, zero)
+            } else {
+                zero
+            };
+            let u = (b * t + f) / e_2;
+            if u < zero {
+                (max_inline(-c / a, zero), zero)
+            } else if u > one {
+                (max_inline((b - c) / a, zero), one)
+            } else {
+                (t, u)
+            }
+        };
+        let on_ray = ray.point_at(t);
+        let on_line = self.from.add(&e.mul(u));
+        (on_ray.sub(&on_line).length_2() == zero).then_some(t)
+    }
+}
+
+impl<P> GeometryDistance<AABB<P>> for Line<P>
+where
+    P: Point,
+    P::Scalar: Float,
+{
+    fn geometry_distance_2(&self, aabb: &AABB<P>) -> P::Scalar {
+        self.distance_2_to_aabb(aabb)
+    }
+}
+
+impl<P> GeometryDistance<Line<P>> for Line<P>
+where
+    P: Point,
+    P::Scalar: Float,
+{
+    fn geometry_distance_2(&self, line: &Line<P>) -> P::Scalar {
+        line.distance_2_to_line(self)
+    }
+}
+
+impl<P, T> NearestNeighborQuery<T> for Line<P>
+where
+    P: Point,
+    P::Scalar: Float,
+    T: GeometryDistance<Line<P>> + RTreeObject<Envelope = AABB<P>>,
+{
+    fn envelope_distance_2(&self, envelope: &AABB<P>) -> P::Scalar {
+        self.distance_2_to_aabb(envelope)
+    }
+
+    fn object_distance_2(&self, object: &T) -> P::Scalar {
+        object.geometry_distance_2(self)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Line;
+    use crate::aabb::AABB;
     use crate::object::PointDistance;
     use approx::*;
 
@@ -136,4 +379,105 @@ mod test {
         let line = Line::new([1, -1], [5, 5]);
         assert_eq!(line.length_2(), 16 + 36);
     }
+
+    #[test]
+    fn line_distance_to_aabb() {
+        let aabb = AABB::from_corners([0.0, 0.0], [1.0, 1.0]);
+
+        let crossing = Line::new([-1.0, 0.5], [2.0, 0.5]);
+        assert_abs_diff_eq!(crossing.distance_2_to_aabb(&aabb), 0.0);
+        let inside = Line::new([0.2, 0.2], [0.8, 0.8]);
+        assert_abs_diff_eq!(inside.distance_2_to_aabb(&aabb), 0.0);
+        let parallel = Line::new([-1.0, 3.0], [2.0, 3.0]);
+        assert_abs_diff_eq!(parallel.distance_2_to_aabb(&aabb), 4.0);
+        let diagonal = Line::new([2.0, 0.0], [0.0, 2.0]);
+        assert_abs_diff_eq!(diagonal.distance_2_to_aabb(&aabb), 0.0);
+        let diagonal = Line::new([3.0, 0.0], [0.0, 3.0]);
+        assert_abs_diff_eq!(diagonal.distance_2_to_aabb(&aabb), 0.5);
+        let corner = Line::new([2.0, 3.0], [5.0, 2.0]);
+        assert_abs_diff_eq!(corner.distance_2_to_aabb(&aabb), 1.0 + 4.0);
+        let degenerate = Line::new([2.0, 2.0], [2.0, 2.0]);
+        assert_abs_diff_eq!(degenerate.distance_2_to_aabb(&aabb), 2.0);
+    }
+
+    #[test]
+    fn line_intersects_aabb() {
+        let aabb = AABB::from_corners([0.0, 0.0], [1.0, 1.0]);
+
+        assert!(Line::new([-1.0, 0.5], [2.0, 0.5]).intersects_aabb(&aabb));
+        assert!(Line::new([0.2, 0.2], [0.8, 0.8]).intersects_aabb(&aabb));
+        assert!(Line::new([2.0, 0.0], [0.0, 2.0]).intersects_aabb(&aabb));
+        assert!(Line::new([1.0, 1.0], [3.0, 3.0]).intersects_aabb(&aabb));
+        assert!(Line::new([0.5, 0.5], [0.5, 0.5]).intersects_aabb(&aabb));
+        assert!(!Line::new([3.0, 0.0], [0.0, 3.0]).intersects_aabb(&aabb));
+        assert!(!Line::new([-1.0, 0.5], [-0.5, 0.5]).intersects_aabb(&aabb));
+        assert!(!Line::new([2.0, 2.0], [2.0, 2.0]).intersects_aabb(&aabb));
+    }
+
+    #[test]
+    fn line_distance_to_line() {
+        let line = Line::new([0.0, 0.0], [2.0, 0.0]);
+
+        let crossing = Line::new([1.0, -1.0], [1.0, 1.0]);
+        assert_abs_diff_eq!(line.distance_2_to_line(&crossing), 0.0);
+        let parallel = Line::new([-1.0, 2.0], [1.0, 2.0]);
+        assert_abs_diff_eq!(line.distance_2_to_line(&parallel), 4.0);
+        let collinear = Line::new([3.0, 0.0], [5.0, 0.0]);
+        assert_abs_diff_eq!(line.distance_2_to_line(&collinear), 1.0);
+        let skewed = Line::new([3.0, 1.0], [4.0, 3.0]);
+        assert_abs_diff_eq!(line.distance_2_to_line(&skewed), 2.0);
+        assert_abs_diff_eq!(skewed.distance_2_to_line(&line), 2.0);
+        let degenerate = Line::new([1.0, 3.0], [1.0, 3.0]);
+        assert_abs_diff_eq!(line.distance_2_to_line(&degenerate), 9.0);
+
+        let line_3d = Line::new([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]);
+        let skewed_3d = Line::new([0.5, -1.0, 1.0], [0.5, 1.0, 1.0]);
+        assert_abs_diff_eq!(line_3d.distance_2_to_line(&skewed_3d), 1.0);
+    }
+
+    #[test]
+    fn ray_line_intersection() {
+        use crate::primitives::{Ray, RayIntersect};
+
+        let line = Line::new([1.0, -1.0], [1.0, 1.0]);
+        let ray = |origin, direction| Ray::new(origin, direction);
+
+        assert_eq!(
+            line.ray_intersection(&ray([0.0, 0.0], [2.0, 0.0])),
+            Some(0.5)
+        );
+        assert_eq!(
+            line.ray_intersection(&ray([0.0, 0.0], [1.0, 1.0])),
+            Some(1.0)
+        );
+        assert_eq!(
+            line.ray_intersection(&ray([2.0, 0.0], [-1.0, 0.0])),
+            Some(1.0)
+        );
+        assert_eq!(line.ray_intersection(&ray([0.0, 0.0], [-1.0, 0.0])), None);
+        assert_eq!(line.ray_intersection(&ray([0.0, 0.0], [1.0, 2.0])), None);
+        assert_eq!(line.ray_intersection(&ray([0.0, 0.0], [0.0, 1.0])), None);
+        // Collinear
+        assert_eq!(
+            line.ray_intersection(&ray([1.0, -3.0], [0.0, 1.0])),
+            Some(2.0)
+        );
+        assert_eq!(
+            line.ray_intersection(&ray([1.0, 3.0], [0.0, -0.5])),
+            Some(4.0)
+        );
+        assert_eq!(
+            line.ray_intersection(&ray([1.0, 0.0], [0.0, 1.0])),
+            Some(0.0)
+        );
+        assert_eq!(line.ray_intersection(&ray([1.0, 2.0], [0.0, 1.0])), None);
+
+        let line_3d = Line::new([1.0, -1.0, 0.0], [1.0, 1.0, 0.0]);
+        let hit = Ray::new([0.0, 0.0, -1.0], [1.0, 0.0, 1.0]);
+        assert_eq!(line_3d.ray_intersection(&hit), Some(1.0));
+        let miss = Ray::new([0.0, 0.0, 1.0], [1.0, 0.0, 0.0]);
+        assert_eq!(line_3d.ray_intersection(&miss), None);
+        let collinear = Ray::new([1.0, -3.0, 0.0], [0.0, 1.0, 0.0]);
+        assert_eq!(line_3d.ray_intersection(&collinear), Some(2.0));
+    }
 }
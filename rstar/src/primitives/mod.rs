@@ -2,14 +2,24 @@
 
 mod cached_envelope;
 mod geom_with_data;
+mod half_space;
 mod line;
+mod moving;
 mod object_ref;
 mod point_with_data;
+mod ray;
 mod rectangle;
+mod temporal;
 
 pub use self::cached_envelope::CachedEnvelope;
 pub use self::geom_with_data::GeomWithData;
+pub use self::half_space::HalfSpace;
 pub use self::line::Line;
+pub use self::moving::{Moving, MovingAABB, MovingPoint, PointAtTime};
 pub use self::object_ref::ObjectRef;
 pub use self::point_with_data::PointWithData;
+pub use self::ray::{Ray, RayIntersect};
 pub use self::rectangle::Rectangle;
+pub use self::temporal::{Temporal, TemporalPoint};
+
+pub(crate) use self::temporal::{spatial_envelope, SpatialQuery};
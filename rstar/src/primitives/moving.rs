@@ -0,0 +1,508 @@
+use crate::algorithm::nearest_neighbor::NearestNeighborQuery;
+use crate::object::RTreeObject;
+use crate::point::{max_inline, min_inline, PointExt};
+use crate::{Envelope, Point, AABB};
+use num_traits::{One, Zero};
+
+/// A position extended by a velocity.
+///
+/// This is the point type of [MovingAABB], the envelope of [Moving] objects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MovingPoint<P> {
+    position: P,
+    velocity: P,
+}
+
+impl<P: Point> MovingPoint<P> {
+    /// Creates a new moving point from a position at time zero and a velocity.
+    pub fn new(position: P, velocity: P) -> Self {
+        MovingPoint { position, velocity }
+    }
+
+    /// Returns the position at time zero.
+    pub fn position(&self) -> &P {
+        &self.position
+    }
+
+    /// Returns the velocity.
+    pub fn velocity(&self) -> &P {
+        &self.velocity
+    }
+}
+
+impl<P: Point> Point for MovingPoint<P> {
+    type Scalar = P::Scalar;
+
+    const DIMENSIONS: usize = 2 * P::DIMENSIONS;
+
+    fn generate(mut generator: impl FnMut(usize) -> Self::Scalar) -> Self {
+        let position = P::generate(&mut generator);
+        let velocity = P::generate(|index| generator(index + P::DIMENSIONS));
+        MovingPoint { position, velocity }
+    }
+
+    fn nth(&self, index: usize) -> Self::Scalar {
+        if index < P::DIMENSIONS {
+            self.position.nth(index)
+        } else {
+            self.velocity.nth(index - P::DIMENSIONS)
+        }
+    }
+
+    fn nth_mut(&mut self, index: usize) -> &mut Self::Scalar {
+        if index < P::DIMENSIONS {
+            self.position.nth_mut(index)
+        } else {
+            self.velocity.nth_mut(index - P::DIMENSIONS)
+        }
+    }
+}
+
+/// A time-parameterized bounding box, used as the envelope of [Moving] objects.
+///
+/// The box bounds both the positions at time zero and the velocities of all contained
+/// objects: at any time `t`, all contained objects lie within [`at_time`](Self::at_time).
+///
+/// Insertion measures a box by the region it covers over a _time horizon_ from time zero
+/// to time one: [area](Envelope::area), [intersection area](Envelope::intersection_area)
+/// and [perimeter](Envelope::perimeter_value) are integrated over the boxes returned by
+/// [`at_time`](Self::at_time) within this horizon. Velocities should thus be given in a time
+/// unit comparable to how far ahead queries will look.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MovingAABB<P>
+where
+    P: Point,
+{
+    aabb: AABB<MovingPoint<P>>,
+}
+
+impl<P: Point> MovingAABB<P> {
+    /// Creates a new box from the region covered at time zero and the range of velocities.
+    pub fn new(positions: AABB<P>, velocities: AABB<P>) -> Self {
+        MovingAABB {
+            aabb: AABB::from_bounds(
+                MovingPoint::new(positions.lower(), velocities.lower()),
+                MovingPoint::new(positions.upper(), velocities.upper()),
+            ),
+        }
+    }
+
+    /// Returns the region covered by this box at a given time.
+    ///
+    /// Negative times are supported as well and yield the region covered in the past.
+    pub fn at_time(&self, time: P::Scalar) -> AABB<P> {
+        let (min, max) = (self.aabb.lower(), self.aabb.upper());
+        let lower = P::generate(|index| {
+            let offset = min_inline(
+                min.velocity.nth(index) * time,
+                max.velocity.nth(index) * time,
+            );
+            min.position.nth(index) + offset
+        });
+        let upper = P::generate(|index| {
+            let offset = max_inline(
+                min.velocity.nth(index) * time,
+                max.velocity.nth(index) * time,
+            );
+            max.position.nth(index) + offset
+        });
+        AABB::from_bounds(lower, upper)
+    }
+
+    /// Integrates a measure of the covered region over the time horizon.
+    ///
+    /// Uses Simpson's rule, which is exact for the areas of two- and three-dimensional boxes.
+    fn integrate(&self, measure: impl Fn(P::Scalar) -> P::Scalar) -> P::Scalar {
+        let one = P::Scalar::one();
+        let two = one + one;
+        let four = two + two;
+        (measure(P::Scalar::zero()) + four * measure(one / two) + measure(one)) / (four + two)
+    }
+}
+
+impl<P> Envelope for MovingAABB<P>
+where
+    P: Point,
+{
+    type Point = MovingPoint<P>;
+
+    fn new_empty() -> Self {
+        MovingAABB {
+            aabb: AABB::new_empty(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.aabb.is_empty()
+    }
+
+    fn contains_point(&self, point: &MovingPoint<P>) -> bool {
+        self.aabb.contains_point(point)
+    }
+
+    fn contains_envelope(&self, other: &Self) -> bool {
+        self.aabb.contains_envelope(&other.aabb)
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.aabb.merge(&other.aabb);
+    }
+
+    fn merged(&self, other: &Self) -> Self {
+        MovingAABB {
+            aabb: self.aabb.merged(&other.aabb),
+        }
+    }
+
+    fn intersects(&self, other: &Self) -> bool {
+        self.aabb.intersects(&other.aabb)
+    }
+
+    fn intersection_area(&self, other: &Self) -> P::Scalar {
+        self.integrate(|time| self.at_time(time).intersection_area(&other.at_time(time)))
+    }
+
+    fn area(&self) -> P::Scalar {
+        self.integrate(|time| self.at_time(time).area())
+    }
+
+    fn distance_2(&self, point: &MovingPoint<P>) -> P::Scalar {
+        self.aabb.distance_2(point)
+    }
+
+    fn min_max_dist_2(&self, point: &MovingPoint<P>) -> P::Scalar {
+        self.aabb.min_max_dist_2(point)
+    }
+
+    fn center(&self) -> MovingPoint<P> {
+        self.aabb.center()
+    }
+
+    fn perimeter_value(&self) -> P::Scalar {
+        self.integrate(|time| self.at_time(time).perimeter_value())
+    }
+
+    fn sort_envelopes<T: RTreeObject<Envelope = Self>>(axis: usize, envelopes: &mut [T]) {
+        envelopes.sort_unstable_by(|l, r| {
+            l.envelope()
+                .aabb
+                .lower()
+                .nth(axis)
+                .partial_cmp(&r.envelope().aabb.lower().nth(axis))
+                .unwrap()
+        });
+    }
+
+    fn partition_envelopes<T: RTreeObject<Envelope = Self>>(
+        axis: usize,
+        envelopes: &mut [T],
+        selection_size: usize,
+    ) {
+        envelopes.select_nth_unstable_by(selection_size, |l, r| {
+            l.envelope()
+                .aabb
+                .lower()
+                .nth(axis)
+                .partial_cmp(&r.envelope().aabb.lower().nth(axis))
+                .unwrap()
+        });
+    }
+}
+
+/// An [RTreeObject] moving with a constant velocity.
+///
+/// An r-tree containing moving objects forms a _TPR-tree_ (time-parameterized r-tree):
+/// each node envelope bounds the positions at time zero and the velocities of all
+/// contained objects, which allows to answer queries for future (or past) times without
+/// updating any position. Insertion uses the regular r*-tree split and reinsertion
+/// strategy, which measures node envelopes over a time horizon (see [MovingAABB]).
+/// Queries are issued by calling
+/// [RTree::locate_in_envelope_intersecting_at](crate::RTree::locate_in_envelope_intersecting_at)
+/// or [RTree::nearest_neighbor_iter_at](crate::RTree::nearest_neighbor_iter_at).
+///
+/// The wrapped object describes the geometry at time zero. An object whose velocity
+/// changes should be removed and re-inserted with its geometry extrapolated back to
+/// time zero. As velocities grow the bounding boxes over time, indexing long time spans
+/// will degrade query performance.
+///
+/// **Note:** as with [GeomWithData](crate::primitives::GeomWithData), you will have to go
+/// through the [`object`][Self::object] method in order to access object-specific methods.
+///
+/// # Example
+/// ```
+/// use rstar::{RTree, AABB};
+/// use rstar::primitives::Moving;
+///
+/// let vessels = RTree::bulk_load(vec![
+///     Moving::new([0.0, 0.0], [1.0, 0.0]),
+///     Moving::new([10.0, 0.0], [-1.0, 0.0]),
+///     Moving::new([0.0, 10.0], [0.0, 0.0]),
+/// ]);
+///
+/// let harbor = AABB::from_corners([4.0, -1.0], [6.0, 1.0]);
+/// assert_eq!(vessels.locate_in_envelope_intersecting_at(harbor, 0.0).count(), 0);
+/// assert_eq!(vessels.locate_in_envelope_intersecting_at(harbor, 5.0).count(), 2);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Moving<T, P> {
+    object: T,
+    velocity: P,
+}
+
+impl<T, P> Moving<T, P>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    /// Creates a new moving object from its geometry at time zero and its velocity.
+    pub fn new(object: T, velocity: P) -> Self {
+        Moving { object, velocity }
+    }
+
+    /// Returns the wrapped object, i.e. the geometry at time zero.
+    pub fn object(&self) -> &T {
+        &self.object
+    }
+
+    /// Returns the velocity of this object.
+    pub fn velocity(&self) -> &P {
+        &self.velocity
+    }
+
+    /// Returns the envelope of this object at a given time.
+    pub fn envelope_at(&self, time: P::Scalar) -> AABB<P> {
+        let offset = self.velocity.mul(time);
+        let envelope = self.object.envelope();
+        AABB::from_bounds(envelope.lower().add(&offset), envelope.upper().add(&offset))
+    }
+}
+
+impl<T, P> RTreeObject for Moving<T, P>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    type Envelope = MovingAABB<P>;
+
+    fn envelope(&self) -> Self::Envelope {
+        MovingAABB::new(
+            self.object.envelope(),
+            AABB::from_point(self.velocity.clone()),
+        )
+    }
+}
+
+/// A point at a given time, looking for the [Moving] objects closest to it.
+///
+/// The distance to an object is measured to its [envelope](Moving::envelope_at) at the
+/// query time, which is exact for moving points. Used by
+/// [RTree::nearest_neighbor_iter_at](crate::RTree::nearest_neighbor_iter_at).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointAtTime<P>
+where
+    P: Point,
+{
+    /// The query point.
+    pub point: P,
+    /// The time at which the moving objects are located.
+    pub time: P::Scalar,
+}
+
+impl<P> PointAtTime<P>
+where
+    P: Point,
+{
+    /// Creates a new query for a point at a given time.
+    pub fn new(point: P, time: P::Scalar) -> Self {
+        PointAtTime { point, time }
+    }
+}
+
+impl<T, P> NearestNeighborQuery<Moving<T, P>> for PointAtTime<P>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    fn envelope_distance_2(&self, envelope: &MovingAABB<P>) -> P::Scalar {
+        envelope.at_time(self.time).distance_2(&self.point)
+    }
+
+    fn object_distance_2(&self, object: &Moving<T, P>) -> P::Scalar {
+        object.envelope_at(self.time).distance_2(&self.point)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Moving, MovingAABB, MovingPoint};
+    use crate::envelope::Envelope;
+    use crate::point::PointExt;
+    use crate::test_utilities::*;
+    use crate::{Point, RTree, RTreeObject, AABB};
+
+    fn create_random_moving_points(num_points: usize) -> Vec<Moving<[f64; 2], [f64; 2]>> {
+        create_random_points(num_points, SEED_1)
+            .into_iter()
+            .zip(create_random_points(num_points, SEED_2))
+            .map(|(position, velocity)| Moving::new(position, velocity.sub(&[0.5, 0.5])))
+            .collect()
+    }
+
+    #[test]
+    fn test_moving_point() {
+        let point = MovingPoint::<[f64; 2]>::generate(|index| index as f64);
+        assert_eq!(point, MovingPoint::new([0.0, 1.0], [2.0, 3.0]));
+        assert_eq!(MovingPoint::<[f64; 2]>::DIMENSIONS, 4);
+        assert_eq!(point.nth(3), 3.0);
+    }
+
+    #[test]
+    fn test_at_time() {
+        let objects = create_random_moving_points(100);
+        let envelope = objects
+            .iter()
+            .map(|object| object.envelope())
+            .fold(MovingAABB::new_empty(), |l, r| l.merged(&r));
+        for time in [-2.0, 0.0, 0.5, 3.0] {
+            let bounds = envelope.at_time(time);
+            for object in &objects {
+                assert!(bounds.contains_envelope(&object.envelope_at(time)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_horizon_measure() {
+        let positions = AABB::from_corners([0.0, 0.0], [2.0, 1.0]);
+        let fixed = MovingAABB::new(positions, AABB::from_point([3.0, -1.0]));
+        assert_eq!(fixed.area(), 2.0);
+        assert_eq!(fixed.perimeter_value(), 3.0);
+
+        // A box of points diverging at unit speed covers an area of t² at time t
+        let diverging = MovingAABB::new(
+            AABB::from_point([0.0, 0.0]),
+            AABB::from_corners([0.0, 0.0], [1.0, 1.0]),
+        );
+        assert_eq!(diverging.area(), 1.0 / 3.0);
+        assert_eq!(diverging.perimeter_value(), 1.0);
+        assert_eq!(diverging.intersection_area(&fixed), 0.0);
+        assert!(diverging.intersection_area(&diverging) > 0.0);
+    }
+
+    #[test]
+    fn test_locate_in_envelope_intersecting_at() {
+        let objects = create_random_moving_points(1000);
+        let mut tree = RTree::new();
+        for object in &objects {
+            tree.insert(*object);
+        }
+        for query in create_random_rectangles(10, SEED_2) {
+            let envelope = query.envelope();
+            for time in [-1.0, 0.0, 0.5, 2.0] {
+                let expected: Vec<_> = objects
+                    .iter()
+                    .filter(|object| object.envelope_at(time).intersects(&envelope))
+                    .collect();
+                let located: Vec<_> = tree
+                    .locate_in_envelope_intersecting_at(envelope, time)
+                    .collect();
+                assert_eq!(located.len(), expected.len());
+                for object in &expected {
+                    assert!(located.contains(object));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_insertion_with_equal_velocities() {
+        use crate::algorithm::selection_functions::{
+            SelectInEnvelopeFuncIntersecting, SelectMovingEnvelopeIntersectingFunction,
+            SelectionFunction,
+        };
+        use core::cell::Cell;
+
+        struct CountingFunction<F> {
+            func: F,
+            visits: Cell<usize>,
+        }
+
+        impl<T: RTreeObject, F: SelectionFunction<T>> SelectionFunction<T> for &CountingFunction<F> {
+            fn should_unpack_parent(&self, envelope: &T::Envelope) -> bool {
+                self.visits.set(self.visits.get() + 1);
+                self.func.should_unpack_parent(envelope)
+            }
+
+            fn should_unpack_leaf(&self, leaf: &T) -> bool {
+                self.func.should_unpack_leaf(leaf)
+            }
+        }
+
+        // With equal velocities, all objects keep their relative positions. The tree
+        // should thus be as good as a tree built from the positions at time zero.
+        let velocity = [0.5, -0.25];
+        let mut moving_tree = RTree::new();
+        let mut static_tree = RTree::new();
+        for point in create_random_points(2000, SEED_1) {
+            moving_tree.insert(Moving::new(point, velocity));
+            static_tree.insert(point);
+        }
+
+        let time = 2.0;
+        let offset = velocity.mul(time);
+        let (mut moving_visits, mut static_visits) = (0, 0);
+        for query in create_random_rectangles(50, SEED_2) {
+            let envelope = query.envelope();
+            let moving = CountingFunction {
+                func: SelectMovingEnvelopeIntersectingFunction::new(
+                    AABB::from_corners(
+                        envelope.lower().add(&offset),
+                        envelope.upper().add(&offset),
+                    ),
+                    time,
+                ),
+                visits: Cell::new(0),
+            };
+            let fixed = CountingFunction {
+                func: SelectInEnvelopeFuncIntersecting::new(envelope),
+                visits: Cell::new(0),
+            };
+            assert_eq!(
+                moving_tree.locate_with_selection_function(&moving).count(),
+                static_tree.locate_with_selection_function(&fixed).count()
+            );
+            moving_visits += moving.visits.get();
+            static_visits += fixed.visits.get();
+        }
+        assert!(
+            moving_visits <= 2 * static_visits,
+            "{} visits, expected about {}",
+            moving_visits,
+            static_visits
+        );
+    }
+
+    #[test]
+    fn test_nearest_neighbor_iter_at() {
+        let objects = create_random_moving_points(1000);
+        let tree = RTree::bulk_load(objects.clone());
+        for query_point in create_random_points(10, SEED_2) {
+            for time in [0.0, 1.0, 5.0] {
+                let mut expected: Vec<_> = objects
+                    .iter()
+                    .map(|object| object.envelope_at(time).distance_2(&query_point))
+                    .collect();
+                expected.sort_by(|l, r| l.partial_cmp(r).unwrap());
+                let nearest: Vec<_> = tree
+                    .nearest_neighbor_iter_at(query_point, time)
+                    .take(10)
+                    .map(|object| object.envelope_at(time).distance_2(&query_point))
+                    .collect();
+                assert_eq!(nearest, expected[..10]);
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::aabb::AABB;
+use crate::envelope::Envelope;
+use crate::object::{Distance, RTreeObject};
+use crate::point::{max_inline, min_inline, Point, PointExt};
+use num_traits::{Bounded, Float, Zero};
+
+/// A half-infinite line defined by an origin and a direction.
+///
+/// Rays are used as query geometry for [RTree::raycast](crate::RTree::raycast). Each point
+/// on the ray is identified by a parameter `t >= 0`, see [Ray::point_at].
+///
+/// Rays require a floating point [Point] type.
+///
+/// # Type parameters
+/// `P`: The ray's [Point] type.
+///
+/// # Example
+/// ```
+/// use rstar::primitives::Ray;
+/// use rstar::AABB;
+///
+/// let ray = Ray::new([0.0, 0.0], [1.0, 0.5]);
+/// let aabb = AABB::from_corners([2.0, -1.0], [3.0, 4.0]);
+/// assert_eq!(ray.aabb_intersection(&aabb), Some(2.0));
+/// assert_eq!(ray.point_at(2.0), [2.0, 1.0]);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ray<P>
+where
+    P: Point,
+{
+    /// The ray's origin.
+    pub origin: P,
+    /// The ray's direction. Does not need to be normalized.
+    pub direction: P,
+}
+
+impl<P> Ray<P>
+where
+    P: Point,
+    P::Scalar: Float,
+{
+    /// Creates a new ray starting at `origin` and pointing towards `direction`.
+    pub fn new(origin: P, direction: P) -> Self {
+        Ray { origin, direction }
+    }
+
+    /// Returns the point `origin + t * direction`.
+    pub fn point_at(&self, t: P::Scalar) -> P {
+        self.origin.add(&self.direction.mul(t))
+    }
+
+    /// Returns the smallest parameter `t` at which this ray enters an AABB.
+    ///
+    /// Returns `Some(0)` if the ray's origin lies within the AABB and `None`
+    /// if the ray misses it.
+    pub fn aabb_intersection(&self, aabb: &AABB<P>) -> Option<P::Scalar> {
+        let zero = P::Scalar::zero();
+        let mut entry = zero;
+        let mut exit = <P::Scalar as Bounded>::max_value();
+        let (lower, upper) = (aabb.lower(), aabb.upper());
+        for i in 0..P::DIMENSIONS {
+            let origin = self.origin.nth(i);
+            let direction = self.direction.nth(i);
+            if direction == zero {
+                // The ray runs parallel to this slab
+                if origin < lower.nth(i) || origin > upper.nth(i) {
+                    return None;
+                }
+            } else {
+                let t1 = (lower.nth(i) - origin) / direction;
+                let t2 = (upper.nth(i) - origin) / direction;
+                entry = max_inline(entry, min_inline(t1, t2));
+                exit = min_inline(exit, max_inline(t1, t2));
+                if entry > exit {
+                    return None;
+                }
+            }
+        }
+        Some(entry)
+    }
+}
+
+/// Defines objects that can be intersected exactly with a [Ray].
+///
+/// [RTree::raycast](crate::RTree::raycast) only checks whether a ray hits the
+/// _envelopes_ of the tree's objects. Objects implementing this trait can instead be
+/// queried with [RTree::raycast_exact](crate::RTree::raycast_exact), which returns only
+/// objects that are actually hit.
+///
+/// This trait is implemented for [Line](crate::primitives::Line),
+/// [Rectangle](crate::primitives::Rectangle) and
+/// [GeomWithData](crate::primitives::GeomWithData).
+pub trait RayIntersect: RTreeObject {
+    /// Returns the smallest parameter `t >= 0` at which a ray hits this object, or `None`
+    /// if the ray misses it.
+    ///
+    /// The returned value **must** not be smaller than the parameter at which the ray
+    /// enters the object's envelope.
+    fn ray_intersection(
+        &self,
+        ray: &Ray<<Self::Envelope as Envelope>::Point>,
+    ) -> Option<Distance<Self>>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::Ray;
+    use crate::AABB;
+
+    #[test]
+    fn ray_aabb_intersection() {
+        let aabb = AABB::from_corners([1.0, 1.0], [2.0, 3.0]);
+
+        assert_eq!(
+            Ray::new([0.0, 2.0], [1.0, 0.0]).aabb_intersection(&aabb),
+            Some(1.0)
+        );
+        assert_eq!(
+            Ray::new([0.0, 0.0], [1.0, 1.0]).aabb_intersection(&aabb),
+            Some(1.0)
+        );
+        assert_eq!(
+            Ray::new([1.5, 2.0], [1.0, 0.0]).aabb_intersection(&aabb),
+            Some(0.0)
+        );
+        assert_eq!(
+            Ray::new([3.0, 2.0], [-2.0, 0.0]).aabb_intersection(&aabb),
+            Some(0.5)
+        );
+        // Touching corner
+        assert_eq!(
+            Ray::new([0.0, 2.0], [1.0, -1.0]).aabb_intersection(&aabb),
+            Some(1.0)
+        );
+
+        assert_eq!(
+            Ray::new([0.0, 2.0], [-1.0, 0.0]).aabb_intersection(&aabb),
+            None
+        );
+        assert_eq!(
+            Ray::new([0.0, 0.0], [0.0, 1.0]).aabb_intersection(&aabb),
+            None
+        );
+        assert_eq!(
+            Ray::new([0.0, 0.0], [1.0, -1.0]).aabb_intersection(&aabb),
+            None
+        );
+        assert_eq!(
+            Ray::new([3.0, 4.0], [1.0, 1.0]).aabb_intersection(&aabb),
+            None
+        );
+    }
+}
@@ -1,7 +1,10 @@
+use crate::algorithm::nearest_neighbor::NearestNeighborQuery;
 use crate::envelope::Envelope;
-use crate::object::{PointDistance, RTreeObject};
+use crate::object::{GeometryDistance, PointDistance, RTreeObject};
 use crate::point::{Point, PointExt};
+use crate::primitives::{Line, Ray, RayIntersect};
 use crate::{aabb::AABB, object::Distance};
+use num_traits::Float;
 
 /// An n-dimensional rectangle defined by its two corners.
 ///
@@ -110,6 +113,50 @@ where
     }
 }
 
+impl<P> RayIntersect for Rectangle<P>
+where
+    P: Point,
+    P::Scalar: Float,
+{
+    fn ray_intersection(&self, ray: &Ray<P>) -> Option<P::Scalar> {
+        ray.aabb_intersection(&self.aabb)
+    }
+}
+
+impl<P> GeometryDistance<AABB<P>> for Rectangle<P>
+where
+    P: Point,
+{
+    fn geometry_distance_2(&self, aabb: &AABB<P>) -> P::Scalar {
+        self.aabb.distance_2_to_aabb(aabb)
+    }
+}
+
+impl<P> GeometryDistance<Line<P>> for Rectangle<P>
+where
+    P: Point,
+    P::Scalar: Float,
+{
+    fn geometry_distance_2(&self, line: &Line<P>) -> P::Scalar {
+        line.distance_2_to_aabb(&self.aabb)
+    }
+}
+
+impl<P, T> NearestNeighborQuery<T> for Rectangle<P>
+where
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+    AABB<P>: NearestNeighborQuery<T>,
+{
+    fn envelope_distance_2(&self, envelope: &AABB<P>) -> P::Scalar {
+        self.aabb.envelope_distance_2(envelope)
+    }
+
+    fn object_distance_2(&self, object: &T) -> P::Scalar {
+        self.aabb.object_distance_2(object)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Rectangle;
@@ -0,0 +1,323 @@
+use crate::algorithm::nearest_neighbor::NearestNeighborQuery;
+use crate::object::{PointDistance, RTreeObject};
+use crate::{Point, RTreeNum, AABB};
+
+/// A spatial point extended by a time coordinate.
+///
+/// This is the point type used by the envelopes of [Temporal] objects. It indexes
+/// time as an additional dimension following all spatial dimensions, while keeping
+/// the spatial point type `P` separate. The time is measured in the same number type
+/// as the spatial coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TemporalPoint<P: Point> {
+    point: P,
+    time: P::Scalar,
+}
+
+impl<P: Point> TemporalPoint<P> {
+    /// Creates a new temporal point from a spatial point and a time.
+    pub fn new(point: P, time: P::Scalar) -> Self {
+        TemporalPoint { point, time }
+    }
+
+    /// Returns the spatial part of this point.
+    pub fn point(&self) -> &P {
+        &self.point
+    }
+
+    /// Returns the time of this point.
+    pub fn time(&self) -> P::Scalar {
+        self.time
+    }
+}
+
+impl<P: Point> Point for TemporalPoint<P> {
+    type Scalar = P::Scalar;
+
+    const DIMENSIONS: usize = P::DIMENSIONS + 1;
+
+    fn generate(mut generator: impl FnMut(usize) -> Self::Scalar) -> Self {
+        let point = P::generate(&mut generator);
+        let time = generator(P::DIMENSIONS);
+        TemporalPoint { point, time }
+    }
+
+    fn nth(&self, index: usize) -> Self::Scalar {
+        if index < P::DIMENSIONS {
+            self.point.nth(index)
+        } else {
+            self.time
+        }
+    }
+
+    fn nth_mut(&mut self, index: usize) -> &mut Self::Scalar {
+        if index < P::DIMENSIONS {
+            self.point.nth_mut(index)
+        } else {
+            &mut self.time
+        }
+    }
+}
+
+/// Returns the spatial part of a temporal envelope.
+pub(crate) fn spatial_envelope<P: Point>(envelope: &AABB<TemporalPoint<P>>) -> AABB<P> {
+    AABB::from_bounds(envelope.lower().point, envelope.upper().point)
+}
+
+/// An [RTreeObject] that is only valid within a time interval.
+///
+/// The object is valid from `valid_from` (inclusive) until `valid_to` (exclusive). Its
+/// envelope spans the object's spatial envelope and its validity interval, allowing
+/// searches to prune both spatially and temporally. Use methods like
+/// [RTree::locate_in_envelope_at](crate::RTree::locate_in_envelope_at) and
+/// [RTree::nearest_neighbor_at](crate::RTree::nearest_neighbor_at) to query the objects
+/// valid at a given time.
+///
+/// Both ends of the interval must be finite, since infinite bounds cannot be measured by
+/// the insertion heuristics. An interval without a known end should instead end at a
+/// finite time beyond all times of interest, e.g. a fixed "open" year. Avoid using the
+/// largest representable value for this as well: such extents dominate the area and
+/// overlap computations and degrade the tree. Once the end becomes known, remove the
+/// object and insert it again with the closed interval.
+///
+/// Since [TemporalPoint] is a [Point] itself, temporal objects can be nested to index a
+/// second, independent time dimension, e.g. transaction time in addition to valid time.
+///
+/// **Note:** as with [GeomWithData](crate::primitives::GeomWithData), you will have to go
+/// through the [`object`][Self::object] method in order to access object-specific methods.
+///
+/// # Example
+/// ```
+/// use rstar::{RTree, AABB};
+/// use rstar::primitives::{Rectangle, Temporal};
+///
+/// // End of all intervals that are still open
+/// const OPEN: f64 = 2100.0;
+///
+/// let parcels = RTree::bulk_load(vec![
+///     Temporal::new(Rectangle::from_corners([0.0, 0.0], [1.0, 1.0]), 1990.0, 2005.0),
+///     Temporal::new(Rectangle::from_corners([0.0, 0.0], [0.5, 1.0]), 2005.0, OPEN),
+///     Temporal::new(Rectangle::from_corners([0.5, 0.0], [1.0, 1.0]), 2005.0, OPEN),
+/// ]);
+///
+/// let area = AABB::from_corners([-1.0, -1.0], [2.0, 2.0]);
+/// assert_eq!(parcels.locate_in_envelope_at(area, 2000.0).count(), 1);
+/// assert_eq!(parcels.locate_in_envelope_at(area, 2005.0).count(), 2);
+/// assert_eq!(parcels.locate_in_envelope_at(area, 1980.0).count(), 0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Temporal<T, S> {
+    object: T,
+    valid_from: S,
+    valid_to: S,
+}
+
+impl<T, S> Temporal<T, S>
+where
+    S: RTreeNum,
+{
+    /// Creates a new object that is valid within `[valid_from, valid_to)`.
+    ///
+    /// # Panics
+    /// Panics if `valid_from` is larger than `valid_to` or if either of them is not finite.
+    pub fn new(object: T, valid_from: S, valid_to: S) -> Self {
+        // Rejects infinite values and NaN
+        let is_finite = |value: S| S::min_value() <= value && value <= S::max_value();
+        assert!(
+            is_finite(valid_from) && is_finite(valid_to),
+            "validity interval must be finite"
+        );
+        assert!(
+            valid_from <= valid_to,
+            "valid_from must not be larger than valid_to"
+        );
+        Temporal {
+            object,
+            valid_from,
+            valid_to,
+        }
+    }
+
+    /// Returns the wrapped object.
+    pub fn object(&self) -> &T {
+        &self.object
+    }
+
+    /// Returns the start of the validity interval (inclusive).
+    pub fn valid_from(&self) -> S {
+        self.valid_from
+    }
+
+    /// Returns the end of the validity interval (exclusive).
+    pub fn valid_to(&self) -> S {
+        self.valid_to
+    }
+
+    /// Returns `true` if this object is valid at a given time.
+    pub fn is_valid_at(&self, time: S) -> bool {
+        self.valid_from <= time && time < self.valid_to
+    }
+}
+
+impl<T, P, S> RTreeObject for Temporal<T, S>
+where
+    S: RTreeNum,
+    P: Point<Scalar = S>,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    type Envelope = AABB<TemporalPoint<P>>;
+
+    fn envelope(&self) -> Self::Envelope {
+        let envelope = self.object.envelope();
+        AABB::from_bounds(
+            TemporalPoint::new(envelope.lower(), self.valid_from),
+            TemporalPoint::new(envelope.upper(), self.valid_to),
+        )
+    }
+}
+
+/// A nearest neighbor query that ignores the time dimension of [Temporal] objects.
+pub(crate) struct SpatialQuery<P>(pub(crate) P);
+
+impl<T, P, S> NearestNeighborQuery<Temporal<T, S>> for SpatialQuery<P>
+where
+    S: RTreeNum,
+    P: Point<Scalar = S>,
+    T: PointDistance + RTreeObject<Envelope = AABB<P>>,
+{
+    fn envelope_distance_2(&self, envelope: &AABB<TemporalPoint<P>>) -> S {
+        spatial_envelope(envelope).distance_2(&self.0)
+    }
+
+    fn object_distance_2(&self, object: &Temporal<T, S>) -> S {
+        object.object.distance_2(&self.0)
+    }
+
+    fn object_distance_2_if_less_or_equal(
+        &self,
+        object: &Temporal<T, S>,
+        max_distance_2: S,
+    ) -> Option<S> {
+        object
+            .object
+            .distance_2_if_less_or_equal(&self.0, max_distance_2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Temporal, TemporalPoint};
+    use crate::object::PointDistance;
+    use crate::primitives::Rectangle;
+    use crate::test_utilities::*;
+    use crate::{Envelope, Point, RTree, RTreeObject, AABB};
+
+    #[test]
+    fn test_temporal_point() {
+        let point = TemporalPoint::<[f64; 2]>::generate(|index| index as f64);
+        assert_eq!(point, TemporalPoint::new([0.0, 1.0], 2.0));
+        assert_eq!(TemporalPoint::<[f64; 2]>::DIMENSIONS, 3);
+        assert_eq!(point.nth(2), 2.0);
+        let mut point = point;
+        *point.nth_mut(2) = 5.0;
+        assert_eq!(point.time(), 5.0);
+    }
+
+    /// Assigns a validity interval within `[0, 1)` to each object.
+    fn with_random_validity<T>(objects: Vec<T>) -> Vec<Temporal<T, f64>> {
+        let intervals = create_random_points(objects.len(), SEED_2);
+        objects
+            .into_iter()
+            .zip(intervals)
+            .map(|(object, [from, to])| Temporal::new(object, from.min(to), from.max(to)))
+            .collect()
+    }
+
+    #[test]
+    fn test_locate_in_envelope_at() {
+        let rectangles = with_random_validity(create_random_rectangles(500, SEED_1));
+        let tree = RTree::bulk_load(rectangles.clone());
+        for query in create_random_rectangles(20, SEED_2) {
+            let envelope = query.envelope().merged(&AABB::from_point([0.5, 0.5]));
+            for time in [0.0, 0.25, 0.5, 0.9] {
+                let expected: Vec<_> = rectangles
+                    .iter()
+                    .filter(|r| {
+                        r.is_valid_at(time) && envelope.contains_envelope(&r.object().envelope())
+                    })
+                    .collect();
+                let located: Vec<_> = tree.locate_in_envelope_at(envelope, time).collect();
+                assert_eq!(located.len(), expected.len());
+                for rectangle in &expected {
+                    assert!(located.contains(rectangle));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_nearest_neighbor_at() {
+        let points = with_random_validity(create_random_points(500, SEED_1));
+        let tree = RTree::bulk_load(points.clone());
+        for query_point in create_random_points(20, SEED_2) {
+            for time in [0.1, 0.5, 0.9] {
+                let expected = points
+                    .iter()
+                    .filter(|p| p.is_valid_at(time))
+                    .map(|p| p.object().distance_2(&query_point))
+                    .min_by(|l, r| l.partial_cmp(r).unwrap());
+                let nearest = tree.nearest_neighbor_at(query_point, time);
+                assert!(nearest.unwrap().is_valid_at(time));
+                assert_eq!(
+                    nearest.map(|p| p.object().distance_2(&query_point)),
+                    expected
+                );
+            }
+        }
+        assert_eq!(tree.nearest_neighbor_at([0.5, 0.5], 2.0), None);
+    }
+
+    #[test]
+    fn test_insert_open_ended_interval() {
+        const OPEN: f64 = 100.0;
+        let mut tree = RTree::new();
+        for (index, rectangle) in create_random_rectangles(500, SEED_1)
+            .into_iter()
+            .enumerate()
+        {
+            let valid_from = (index % 10) as f64 / 10.0;
+            let valid_to = if index % 3 == 0 {
+                OPEN
+            } else {
+                valid_from + 0.2
+            };
+            tree.insert(Temporal::new(rectangle, valid_from, valid_to));
+        }
+        assert_eq!(tree.size(), 500);
+        let everything = AABB::from_corners([-1.0, -1.0], [2.0, 2.0]);
+        let located: Vec<_> = tree.locate_in_envelope_at(everything, 50.0).collect();
+        assert_eq!(located.len(), 167);
+        assert!(located.iter().all(|r| r.valid_to() == OPEN));
+    }
+
+    #[test]
+    #[should_panic(expected = "validity interval must be finite")]
+    fn test_infinite_interval() {
+        Temporal::new([0.0, 0.0], 0.0, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_half_open_interval() {
+        let tree = RTree::bulk_load(vec![
+            Temporal::new(Rectangle::from_corners([0.0, 0.0], [1.0, 1.0]), 0.0, 1.0),
+            Temporal::new(Rectangle::from_corners([0.0, 0.0], [1.0, 1.0]), 1.0, 2.0),
+        ]);
+        let envelope = AABB::from_corners([0.0, 0.0], [1.0, 1.0]);
+        let located: Vec<_> = tree.locate_in_envelope_at(envelope, 1.0).collect();
+        assert_eq!(located.len(), 1);
+        assert_eq!(located[0].valid_from(), 1.0);
+        assert_eq!(tree.locate_in_envelope_at(envelope, 2.0).count(), 0);
+    }
+}
@@ -1,19 +1,28 @@
+use crate::algorithm::batch;
+use crate::algorithm::best_first::BestFirstFunction;
+use crate::algorithm::closest_pairs;
 use crate::algorithm::nearest_neighbor;
 use crate::algorithm::nearest_neighbor::NearestNeighborDistance2Iterator;
 use crate::algorithm::nearest_neighbor::NearestNeighborIterator;
+use crate::algorithm::nearest_neighbor::NearestNeighborQuery;
 use crate::algorithm::removal;
+use crate::algorithm::reverse_nearest_neighbor;
 use crate::algorithm::selection_functions::*;
 use crate::envelope::Envelope;
+use crate::metric::{Metric, MetricQuery, Periodic};
 use crate::node::ParentNode;
-use crate::object::{PointDistance, RTreeObject};
+use crate::object::{PointDistance, PointObject, RTreeObject};
 use crate::params::{verify_parameters, DefaultParams, InsertionStrategy, RTreeParams};
+use crate::primitives::{Line, Moving, PointAtTime, Ray, RayIntersect, SpatialQuery, Temporal};
 use crate::{algorithm::iterators::*, object::Distance};
+use crate::{Point, RTreeNum, AABB};
 
 use crate::algorithm::bulk_load;
 use core::ops::ControlFlow;
+use num_traits::{Float, One};
 
 #[cfg(not(test))]
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -314,6 +323,29 @@ where
         RTreeIterator::new(&self.root, SelectAllFunc)
     }
 
+    /// Returns a parallel iterator over all elements contained in the tree.
+    ///
+    /// The work is split between threads at the top levels of the tree. The order
+    /// in which the elements are returned is not specified.
+    ///
+    /// # Example
+    /// ```
+    /// use rayon::prelude::*;
+    /// use rstar::RTree;
+    ///
+    /// let tree = RTree::bulk_load(vec![(0.0, 0.1), (0.3, 0.2), (0.4, 0.2)]);
+    /// let sum: f64 = tree.par_iter().map(|point| point.0).sum();
+    /// assert!((sum - 0.7).abs() < 1e-10);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> ParRTreeIterator<'_, T>
+    where
+        T: Sync,
+        T::Envelope: Sync,
+    {
+        ParRTreeIterator::new(&self.root, SelectAllFunc)
+    }
+
     /// Returns an iterator over all mutable elements contained in the tree.
     ///
     /// The order in which the elements are returned is not specified.
@@ -357,6 +389,18 @@ where
         LocateInEnvelopeMut::new(&mut self.root, SelectInEnvelopeFunction::new(envelope))
     }
 
+    /// Parallel variant of [locate_in_envelope](#method.locate_in_envelope).
+    ///
+    /// The work is split between threads at the top levels of the tree.
+    #[cfg(feature = "rayon")]
+    pub fn par_locate_in_envelope(&self, envelope: T::Envelope) -> ParLocateInEnvelope<'_, T>
+    where
+        T: Sync,
+        T::Envelope: Sync,
+    {
+        ParLocateInEnvelope::new(&self.root, SelectInEnvelopeFunction::new(envelope))
+    }
+
     /// Variant of [`locate_in_envelope`][Self::locate_in_envelope] using internal iteration.
     pub fn locate_in_envelope_int<'a, V, B>(
         &'a self,
@@ -465,6 +509,278 @@ where
         )
     }
 
+    /// Returns `true` if any element is fully contained within an envelope.
+    ///
+    /// This is equivalent to `locate_in_envelope(envelope).next().is_some()` but stops
+    /// at the first hit without allocating.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::{RTree, AABB};
+    /// let tree = RTree::bulk_load(vec![[0.0, 0.0], [1.0, 1.0]]);
+    /// assert!(tree.any_in_envelope(AABB::from_corners([0.5, 0.5], [2.0, 2.0])));
+    /// assert!(!tree.any_in_envelope(AABB::from_corners([0.2, 0.2], [0.8, 0.8])));
+    /// ```
+    pub fn any_in_envelope(&self, envelope: T::Envelope) -> bool {
+        let func = SelectInEnvelopeFunction::new(envelope.clone());
+        any_selected(
+            &self.root,
+            &SelectContainedParentsFunction::new(envelope, func),
+        )
+    }
+
+    /// Returns `true` if any element's envelope intersects a given envelope.
+    ///
+    /// This is equivalent to `locate_in_envelope_intersecting(envelope).next().is_some()`
+    /// but stops at the first hit without allocating.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::{RTree, AABB};
+    /// use rstar::primitives::Rectangle;
+    ///
+    /// let tree = RTree::bulk_load(vec![Rectangle::from_corners([0.0, 0.0], [1.0, 1.0])]);
+    /// assert!(tree.any_intersecting(AABB::from_corners([0.5, 0.5], [2.0, 2.0])));
+    /// assert!(!tree.any_intersecting(AABB::from_corners([1.5, 1.5], [2.0, 2.0])));
+    /// ```
+    pub fn any_intersecting(&self, envelope: T::Envelope) -> bool {
+        let func = SelectInEnvelopeFuncIntersecting::new(envelope.clone());
+        any_selected(
+            &self.root,
+            &SelectContainedParentsFunction::new(envelope, func),
+        )
+    }
+
+    /// Returns all elements whose envelope fully contains a given envelope.
+    ///
+    /// This is the reverse of [locate_in_envelope](#method.locate_in_envelope), e.g. to
+    /// find all areas that completely cover a given parcel. An envelope that touches
+    /// the boundary of an element's envelope is still considered to be contained.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::{RTree, AABB};
+    /// use rstar::primitives::Rectangle;
+    ///
+    /// let tree = RTree::<Rectangle<_>>::bulk_load(vec![
+    ///   AABB::from_corners([0.0, 0.0], [1.0, 1.0]).into(),
+    ///   AABB::from_corners([0.0, 0.0], [0.5, 0.5]).into(),
+    ///   AABB::from_corners([0.5, 0.5], [1.0, 1.0]).into(),
+    /// ]);
+    ///
+    /// let parcel = AABB::from_corners([0.1, 0.1], [0.2, 0.2]);
+    /// assert_eq!(tree.locate_containing_envelope(parcel).count(), 2);
+    /// let parcel = AABB::from_corners([0.4, 0.4], [0.6, 0.6]);
+    /// assert_eq!(tree.locate_containing_envelope(parcel).count(), 1);
+    /// ```
+    pub fn locate_containing_envelope(
+        &self,
+        envelope: T::Envelope,
+    ) -> LocateContainingEnvelope<'_, T> {
+        LocateContainingEnvelope::new(&self.root, SelectContainingEnvelopeFunction::new(envelope))
+    }
+
+    /// Mutable variant of [locate_containing_envelope](#method.locate_containing_envelope)
+    pub fn locate_containing_envelope_mut(
+        &mut self,
+        envelope: T::Envelope,
+    ) -> LocateContainingEnvelopeMut<'_, T> {
+        LocateContainingEnvelopeMut::new(
+            &mut self.root,
+            SelectContainingEnvelopeFunction::new(envelope),
+        )
+    }
+
+    /// Locates elements in the r-tree whose envelope intersects a line segment.
+    ///
+    /// In contrast to querying the segment's bounding box with
+    /// [locate_in_envelope_intersecting](#method.locate_in_envelope_intersecting), this
+    /// checks each envelope against the segment itself. This avoids most false positives
+    /// for long, diagonal segments.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::{RTree, RTreeObject};
+    /// use rstar::primitives::{Line, Rectangle};
+    ///
+    /// let parcels = RTree::bulk_load(vec![
+    ///     Rectangle::from_corners([0.0, 0.0], [1.0, 1.0]),
+    ///     Rectangle::from_corners([4.0, 4.0], [5.0, 5.0]),
+    ///     Rectangle::from_corners([0.0, 4.0], [1.0, 5.0]),
+    /// ]);
+    ///
+    /// let cable = Line::new([0.5, 0.5], [4.5, 4.5]);
+    /// assert_eq!(parcels.locate_intersecting_segment(cable).count(), 2);
+    /// assert_eq!(parcels.locate_in_envelope_intersecting(cable.envelope()).count(), 3);
+    /// ```
+    pub fn locate_intersecting_segment<P>(
+        &self,
+        segment: Line<P>,
+    ) -> LocateIntersectingSegment<'_, T>
+    where
+        P: Point,
+        P::Scalar: Float,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        LocateIntersectingSegment::new(&self.root, SelectSegmentIntersectingFunction::new(segment))
+    }
+
+    /// Mutable variant of [locate_intersecting_segment](#method.locate_intersecting_segment)
+    pub fn locate_intersecting_segment_mut<P>(
+        &mut self,
+        segment: Line<P>,
+    ) -> LocateIntersectingSegmentMut<'_, T>
+    where
+        P: Point,
+        P::Scalar: Float,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        LocateIntersectingSegmentMut::new(
+            &mut self.root,
+            SelectSegmentIntersectingFunction::new(segment),
+        )
+    }
+
+    /// Locates elements within a longitude / latitude box that may cross the antimeridian.
+    ///
+    /// The tree's points are expected to store the longitude in degrees within
+    /// `[-180, 180]` as first and the latitude in degrees as second coordinate. If the
+    /// longitude of `lower` is larger than the longitude of `upper`, the box is considered to
+    /// cross the antimeridian and wraps around from `180` to `-180`. Otherwise, this is
+    /// equivalent to [locate_in_envelope](#method.locate_in_envelope).
+    ///
+    /// See also [Haversine](crate::metric::Haversine) for geodesic nearest neighbor and
+    /// within-distance queries.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    ///
+    /// let tree = RTree::bulk_load(vec![[179.0, -17.0], [-179.5, -16.0], [0.0, -16.5]]);
+    /// // A box between 178°E and 178°W
+    /// let located = tree.locate_in_geographic_envelope([178.0, -18.0], [-178.0, -15.0]);
+    /// assert_eq!(located.count(), 2);
+    /// ```
+    pub fn locate_in_geographic_envelope<P>(
+        &self,
+        lower: P,
+        upper: P,
+    ) -> LocateInGeographicEnvelope<'_, T, P>
+    where
+        P: Point,
+        P::Scalar: Float,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        LocateInGeographicEnvelope::new(
+            &self.root,
+            SelectInGeographicEnvelopeFunction::new(lower, upper),
+        )
+    }
+
+    /// Locates elements intersecting a longitude / latitude box that may cross the
+    /// antimeridian.
+    ///
+    /// See [locate_in_geographic_envelope](#method.locate_in_geographic_envelope) for
+    /// how the box is interpreted.
+    pub fn locate_in_geographic_envelope_intersecting<P>(
+        &self,
+        lower: P,
+        upper: P,
+    ) -> LocateInGeographicEnvelopeIntersecting<'_, T, P>
+    where
+        P: Point,
+        P::Scalar: Float,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        LocateInGeographicEnvelopeIntersecting::new(
+            &self.root,
+            SelectInGeographicEnvelopeFuncIntersecting::new(lower, upper),
+        )
+    }
+
+    /// Locates elements in the r-tree whose envelope intersects a ball.
+    ///
+    /// The ball is given by its center and its _squared_ radius. In contrast to
+    /// [locate_within_distance](#method.locate_within_distance), this only requires
+    /// the elements' envelopes and thus works for any kind of [RTreeObject].
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// use rstar::primitives::Rectangle;
+    ///
+    /// let tree = RTree::bulk_load(vec![
+    ///     Rectangle::from_corners([0.0, 0.0], [1.0, 1.0]),
+    ///     Rectangle::from_corners([1.5, 0.0], [3.0, 1.0]),
+    ///     Rectangle::from_corners([2.0, 2.0], [3.0, 3.0]),
+    /// ]);
+    ///
+    /// assert_eq!(tree.locate_intersecting_ball([0.0, 0.0], 2.0 * 2.0).count(), 2);
+    /// assert_eq!(tree.locate_in_ball([0.0, 0.0], 2.0 * 2.0).count(), 1);
+    /// ```
+    pub fn locate_intersecting_ball<P>(
+        &self,
+        center: P,
+        squared_radius: P::Scalar,
+    ) -> LocateIntersectingBall<'_, T>
+    where
+        P: Point,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        LocateIntersectingBall::new(
+            &self.root,
+            SelectIntersectingBallFunction::new(center, squared_radius),
+        )
+    }
+
+    /// Mutable variant of [locate_intersecting_ball](#method.locate_intersecting_ball)
+    pub fn locate_intersecting_ball_mut<P>(
+        &mut self,
+        center: P,
+        squared_radius: P::Scalar,
+    ) -> LocateIntersectingBallMut<'_, T>
+    where
+        P: Point,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        LocateIntersectingBallMut::new(
+            &mut self.root,
+            SelectIntersectingBallFunction::new(center, squared_radius),
+        )
+    }
+
+    /// Locates elements in the r-tree whose envelope lies fully within a ball.
+    ///
+    /// The ball is given by its center and its _squared_ radius. Subtrees lying fully
+    /// within the ball are returned without checking each element.
+    /// See [locate_intersecting_ball](#method.locate_intersecting_ball) for an example.
+    pub fn locate_in_ball<P>(&self, center: P, squared_radius: P::Scalar) -> LocateInBall<'_, T>
+    where
+        P: Point,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        LocateInBall::new(
+            &self.root,
+            SelectInBallFunction::new(center, squared_radius),
+        )
+    }
+
+    /// Mutable variant of [locate_in_ball](#method.locate_in_ball)
+    pub fn locate_in_ball_mut<P>(
+        &mut self,
+        center: P,
+        squared_radius: P::Scalar,
+    ) -> LocateInBallMut<'_, T>
+    where
+        P: Point,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        LocateInBallMut::new(
+            &mut self.root,
+            SelectInBallFunction::new(center, squared_radius),
+        )
+    }
+
     /// Variant of [`locate_in_envelope_intersecting`][Self::locate_in_envelope_intersecting] using internal iteration.
     pub fn locate_in_envelope_intersecting_int<'a, V, B>(
         &'a self,
@@ -511,6 +827,20 @@ where
         SelectionIterator::new(&self.root, selection_function)
     }
 
+    /// Returns `true` if any element is selected by a selection function.
+    ///
+    /// Unlike `locate_with_selection_function(..).next().is_some()`, this stops at the
+    /// first selected element without allocating. Any node for which
+    /// [`SelectionFunction::select_parent`] returns
+    /// [`ParentSelection::SelectAll`](crate::ParentSelection::SelectAll) is known to contain
+    /// a selected element and is not visited.
+    pub fn any_with_selection_function<S: SelectionFunction<T>>(
+        &self,
+        selection_function: S,
+    ) -> bool {
+        any_selected(&self.root, &selection_function)
+    }
+
     /// Mutable variant of [`locate_with_selection_function`](#method.locate_with_selection_function).
     pub fn locate_with_selection_function_mut<S: SelectionFunction<T>>(
         &mut self,
@@ -519,6 +849,22 @@ where
         SelectionIteratorMut::new(&mut self.root, selection_function)
     }
 
+    /// Parallel variant of [`locate_with_selection_function`](#method.locate_with_selection_function).
+    ///
+    /// The work is split between threads at the top levels of the tree.
+    #[cfg(feature = "rayon")]
+    pub fn par_locate_with_selection_function<S>(
+        &self,
+        selection_function: S,
+    ) -> ParSelectionIterator<'_, T, S>
+    where
+        S: SelectionFunction<T> + Send + Sync,
+        T: Sync,
+        T::Envelope: Sync,
+    {
+        ParSelectionIterator::new(&self.root, selection_function)
+    }
+
     /// Returns all possible intersecting objects of this and another tree.
     ///
     /// This will return all objects whose _envelopes_ intersect. No geometric intersection
@@ -533,60 +879,320 @@ where
         IntersectionIterator::new(self.root(), other.root())
     }
 
-    /// Returns the tree's root node.
+    /// Returns all possible intersecting pairs of objects within this tree.
     ///
-    /// For some algorithms and use cases, knowledge of the tree's internal structure may be required.
-    /// For these cases, this method serves as an entry point, allowing flexible iteration and
-    /// aggregation strategies. The examples below demonstrate two different traversal
-    /// approaches: top-down (pre-order: process parent then children) and bottom-up (post-order:
-    /// process children then parent). Post-order is essential when parent values depend on
-    /// already-computed child values, such as recomputing envelopes from child data.
+    /// This will return all pairs of distinct objects whose _envelopes_ intersect. Each
+    /// unordered pair is returned exactly once and no object is paired with itself. This is
+    /// more efficient than calling
+    /// [`intersection_candidates_with_other_tree`](Self::intersection_candidates_with_other_tree)
+    /// with the same tree twice.
     ///
-    /// # Examples
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// use rstar::primitives::Rectangle;
     ///
+    /// let tree = RTree::bulk_load(vec![
+    ///     Rectangle::from_corners([0.0, 0.0], [2.0, 2.0]),
+    ///     Rectangle::from_corners([1.0, 1.0], [3.0, 3.0]),
+    ///     Rectangle::from_corners([5.0, 5.0], [6.0, 6.0]),
+    /// ]);
+    /// assert_eq!(tree.self_intersection_candidates().count(), 1);
     /// ```
-    /// use rstar::{RTree, RTreeNode, ParentNode};
+    pub fn self_intersection_candidates(&self) -> SelfIntersectionIterator<'_, T> {
+        SelfIntersectionIterator::new(self.root())
+    }
+
+    /// Returns all pairs of distinct objects within this tree that lie within a given distance.
+    ///
+    /// Just like [`self_intersection_candidates`](Self::self_intersection_candidates), each
+    /// unordered pair is returned exactly once and no object is paired with itself.
+    /// See [`within_distance_join`](Self::within_distance_join) for more information.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    ///
+    /// let tree = RTree::bulk_load(vec![[0.0, 0.0], [0.0, 1.0], [5.0, 0.0]]);
+    /// let pairs: Vec<_> = tree.self_within_distance_join(1.0).collect();
+    /// assert_eq!(pairs.len(), 1);
+    /// ```
+    pub fn self_within_distance_join<P>(
+        &self,
+        max_distance_2: Distance<T>,
+    ) -> SelfWithinDistanceJoinIterator<'_, T>
+    where
+        P: Point,
+        T: NearestNeighborQuery<T> + RTreeObject<Envelope = AABB<P>>,
+    {
+        SelfWithinDistanceJoinIterator::new(self.root(), max_distance_2)
+    }
+
+    /// Returns all pairs of objects of this and another tree that lie within a given distance.
+    ///
+    /// Node pairs of both trees are pruned by the distance between their envelopes. Each
+    /// remaining pair of objects is then checked with
+    /// [`NearestNeighborQuery::object_distance_2_if_less_or_equal`], which uses
+    /// [`PointDistance`] when `T` is a point.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// use rstar::primitives::Line;
+    ///
+    /// let rivers = RTree::bulk_load(vec![Line::new([0.0, 0.0], [100.0, 0.0])]);
+    /// let buildings = RTree::bulk_load(vec![[10.0, 30.0], [50.0, -60.0], [80.0, 45.0]]);
+    ///
+    /// let mut close = rivers
+    ///     .within_distance_join(&buildings, 50.0 * 50.0)
+    ///     .map(|(_, building)| *building)
+    ///     .collect::<Vec<_>>();
+    /// close.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    /// assert_eq!(close, vec![[10.0, 30.0], [80.0, 45.0]]);
+    /// ```
+    pub fn within_distance_join<'a, U, P>(
+        &'a self,
+        other: &'a RTree<U>,
+        max_distance_2: Distance<T>,
+    ) -> WithinDistanceJoinIterator<'a, T, U>
+    where
+        P: Point,
+        T: NearestNeighborQuery<U> + RTreeObject<Envelope = AABB<P>>,
+        U: RTreeObject<Envelope = AABB<P>>,
+    {
+        WithinDistanceJoinIterator::new(self.root(), other.root(), max_distance_2)
+    }
+
+    /// Returns the `k` closest pairs of objects between this and another tree.
+    ///
+    /// Each pair is returned together with the squared distance between its objects.
+    /// The pairs are sorted by distance, the closest pair coming first. Fewer than `k`
+    /// pairs are returned if the trees don't contain enough objects.
+    ///
+    /// Both trees are searched together, visiting pairs of nodes in the order of the distance
+    /// between their envelopes. The distance between two objects is defined by
+    /// [`NearestNeighborQuery`], which uses [`PointDistance`] when `T` is a point.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// use rstar::primitives::Line;
+    ///
+    /// let pipes1 = RTree::bulk_load(vec![
+    ///     Line::new([0.0, 0.0], [10.0, 0.0]),
+    ///     Line::new([10.0, 0.0], [10.0, 10.0]),
+    /// ]);
+    /// let pipes2 = RTree::bulk_load(vec![
+    ///     Line::new([0.0, 3.0], [7.0, 3.0]),
+    ///     Line::new([12.0, 5.0], [12.0, 20.0]),
+    /// ]);
+    ///
+    /// let closest = pipes1.closest_pairs(&pipes2, 1);
+    /// let (pipe1, pipe2, clearance_2) = closest[0];
+    /// assert_eq!(pipe1, &Line::new([10.0, 0.0], [10.0, 10.0]));
+    /// assert_eq!(pipe2, &Line::new([12.0, 5.0], [12.0, 20.0]));
+    /// assert_eq!(clearance_2, 4.0);
+    /// ```
+    pub fn closest_pairs<'a, U, P>(
+        &'a self,
+        other: &'a RTree<U>,
+        k: usize,
+    ) -> Vec<(&'a T, &'a U, Distance<T>)>
+    where
+        P: Point,
+        T: NearestNeighborQuery<U> + RTreeObject<Envelope = AABB<P>>,
+        U: RTreeObject<Envelope = AABB<P>>,
+    {
+        closest_pairs::closest_pairs(self.root(), other.root(), k)
+    }
+
+    /// Returns the `k` nearest neighbors in another tree for each object of this tree.
+    ///
+    /// For each element of this tree, the returned iterator yields the element together with
+    /// up to `k` elements of `other` and their squared distances, sorted by distance.
+    /// The iteration order of the elements of this tree is unspecified.
+    ///
+    /// The elements of this tree are processed in groups of leaves sharing the same parent
+    /// node. A single best-first search over `other` is run for each group, which skips all
+    /// nodes farther away from the group's envelope than the current `k`-th nearest neighbor
+    /// of every group member. Every search starts at the root of `other`: compared to a
+    /// separate query for each element, the number of searches is divided by the tree's
+    /// node size, but each search visits the nodes close to _any_ group member.
+    ///
+    /// The distance between two objects is
+    /// defined by [`NearestNeighborQuery`], which is implemented for points,
+    /// [AABB](crate::AABB), [Line](crate::primitives::Line),
+    /// [Rectangle](crate::primitives::Rectangle) and
+    /// [GeomWithData](crate::primitives::GeomWithData).
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
     /// use rstar::primitives::GeomWithData;
     ///
-    /// type PointWithLabel = GeomWithData<[f32; 2], String>;
+    /// let addresses = RTree::bulk_load(vec![
+    ///     GeomWithData::new([0.0, 0.0], "Main Street 1"),
+    ///     GeomWithData::new([5.0, 5.0], "Harbor Road 3"),
+    /// ]);
+    /// let stops = RTree::bulk_load(vec![[1.0, 0.0], [0.0, 2.0], [6.0, 5.0], [9.0, 9.0]]);
     ///
-    /// // Top-down traversal: Start from root, recurse down to leaves
-    /// fn paths_top_down(tree: &RTree<PointWithLabel>) -> Vec<String> {
-    ///     let mut node_counter = 0;
-    ///     
-    ///     fn traverse(
-    ///         parent: &ParentNode<PointWithLabel>,
-    ///         path: String,
-    ///         paths: &mut Vec<String>,
-    ///         node_counter: &mut usize
-    ///     ) {
-    ///         for child in parent.children() {
-    ///             *node_counter += 1;
-    ///             let child_path = if path.is_empty() {
-    ///                 format!("Node{}", *node_counter)
-    ///             } else {
-    ///                 format!("{} -> Node{}", path, *node_counter)
-    ///             };
-    ///             
-    ///             match child {
-    ///                 RTreeNode::Leaf(item) => {
-    ///                     // Record the complete path to this leaf
-    ///                     paths.push(format!("{} -> Leaf({})", child_path, item.data));
-    ///                 }
-    ///                 RTreeNode::Parent(child_parent) => {
-    ///                     // Continue building the path downward
-    ///                     traverse(child_parent, child_path, paths, node_counter);
-    ///                 }
-    ///             }
+    /// for (address, nearest_stops) in addresses.k_nearest_neighbors_join(&stops, 2) {
+    ///     match address.data {
+    ///         "Main Street 1" => {
+    ///             assert_eq!(nearest_stops, vec![(&[1.0, 0.0], 1.0), (&[0.0, 2.0], 4.0)]);
     ///         }
+    ///         _ => assert_eq!(nearest_stops[0], (&[6.0, 5.0], 1.0)),
     ///     }
-    ///     
-    ///     let mut paths = Vec::new();
-    ///     traverse(tree.root(), "Node0".to_string(), &mut paths, &mut node_counter);
-    ///     paths
     /// }
-    ///
-    /// // Bottom-up traversal: Aggregate child envelopes into parents (post-order)
+    /// ```
+    pub fn k_nearest_neighbors_join<'a, U, P>(
+        &'a self,
+        other: &'a RTree<U>,
+        k: usize,
+    ) -> KNearestNeighborsJoinIterator<'a, T, U>
+    where
+        P: Point,
+        T: NearestNeighborQuery<U> + RTreeObject<Envelope = AABB<P>>,
+        U: RTreeObject<Envelope = AABB<P>>,
+    {
+        KNearestNeighborsJoinIterator::new(self.root(), other.root(), k)
+    }
+
+    /// Returns all elements whose envelope is hit by a ray.
+    ///
+    /// The iterator yields `(element, t)` tuples sorted by the ray parameter `t` at which
+    /// the element's envelope is entered, see [Ray::point_at](crate::primitives::Ray::point_at).
+    /// Elements are returned lazily, the first hit can be found without visiting
+    /// the whole tree.
+    ///
+    /// No exact intersection test is performed. Use [`raycast_exact`](Self::raycast_exact)
+    /// for elements implementing [`RayIntersect`](crate::primitives::RayIntersect).
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// use rstar::primitives::{Ray, Rectangle};
+    ///
+    /// let tree = RTree::bulk_load(vec![
+    ///     Rectangle::from_corners([2.0, -1.0], [3.0, 1.0]),
+    ///     Rectangle::from_corners([5.0, -1.0], [6.0, 1.0]),
+    ///     Rectangle::from_corners([2.0, 3.0], [3.0, 4.0]),
+    /// ]);
+    ///
+    /// let ray = Ray::new([0.0, 0.0], [1.0, 0.0]);
+    /// let hits: Vec<_> = tree.raycast(ray).map(|(_, t)| t).collect();
+    /// assert_eq!(hits, vec![2.0, 5.0]);
+    /// ```
+    pub fn raycast<P>(&self, ray: Ray<P>) -> RaycastIterator<'_, T>
+    where
+        P: Point,
+        P::Scalar: Float,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        RaycastIterator::new(&self.root, ray)
+    }
+
+    /// Returns all elements hit by a ray.
+    ///
+    /// In contrast to [`raycast`](Self::raycast), elements are checked with
+    /// [`RayIntersect::ray_intersection`](crate::primitives::RayIntersect::ray_intersection).
+    /// The iterator yields `(element, t)` tuples sorted by the ray parameter `t` at which
+    /// each element is hit.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// use rstar::primitives::{Line, Ray};
+    ///
+    /// let tree = RTree::bulk_load(vec![
+    ///     Line::new([2.0, 2.0], [4.0, 0.0]),
+    ///     Line::new([0.5, 0.0], [2.0, 0.9]),
+    /// ]);
+    ///
+    /// let ray = Ray::new([0.0, 0.0], [1.0, 1.0]);
+    /// // The ray passes through the second line's envelope, but misses the line itself
+    /// assert_eq!(tree.raycast(ray).count(), 2);
+    /// let hits: Vec<_> = tree.raycast_exact(ray).collect();
+    /// assert_eq!(hits, vec![(&Line::new([2.0, 2.0], [4.0, 0.0]), 2.0)]);
+    /// ```
+    pub fn raycast_exact<P>(&self, ray: Ray<P>) -> RaycastIterator<'_, T>
+    where
+        P: Point,
+        P::Scalar: Float,
+        T: RayIntersect + RTreeObject<Envelope = AABB<P>>,
+    {
+        RaycastIterator::new_exact(&self.root, ray)
+    }
+
+    /// Returns all elements of the tree in ascending order of a custom score.
+    ///
+    /// The search is driven by a [BestFirstFunction] defining a score for each object and
+    /// a lower bound of these scores for each node. This allows ordered queries that are
+    /// not covered by the various `nearest_neighbor` methods, e.g. using weighted or
+    /// time dependent distances. The iterator yields `(object, score)` tuples.
+    ///
+    /// See [BestFirstFunction] for an example.
+    pub fn best_first_iter<F>(&self, function: F) -> BestFirstIterator<'_, T, F>
+    where
+        F: BestFirstFunction<T>,
+    {
+        BestFirstIterator::new(&self.root, function)
+    }
+
+    /// Returns the tree's root node.
+    ///
+    /// For some algorithms and use cases, knowledge of the tree's internal structure may be required.
+    /// For these cases, this method serves as an entry point, allowing flexible iteration and
+    /// aggregation strategies. The examples below demonstrate two different traversal
+    /// approaches: top-down (pre-order: process parent then children) and bottom-up (post-order:
+    /// process children then parent). Post-order is essential when parent values depend on
+    /// already-computed child values, such as recomputing envelopes from child data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rstar::{RTree, RTreeNode, ParentNode};
+    /// use rstar::primitives::GeomWithData;
+    ///
+    /// type PointWithLabel = GeomWithData<[f32; 2], String>;
+    ///
+    /// // Top-down traversal: Start from root, recurse down to leaves
+    /// fn paths_top_down(tree: &RTree<PointWithLabel>) -> Vec<String> {
+    ///     let mut node_counter = 0;
+    ///     
+    ///     fn traverse(
+    ///         parent: &ParentNode<PointWithLabel>,
+    ///         path: String,
+    ///         paths: &mut Vec<String>,
+    ///         node_counter: &mut usize
+    ///     ) {
+    ///         for child in parent.children() {
+    ///             *node_counter += 1;
+    ///             let child_path = if path.is_empty() {
+    ///                 format!("Node{}", *node_counter)
+    ///             } else {
+    ///                 format!("{} -> Node{}", path, *node_counter)
+    ///             };
+    ///             
+    ///             match child {
+    ///                 RTreeNode::Leaf(item) => {
+    ///                     // Record the complete path to this leaf
+    ///                     paths.push(format!("{} -> Leaf({})", child_path, item.data));
+    ///                 }
+    ///                 RTreeNode::Parent(child_parent) => {
+    ///                     // Continue building the path downward
+    ///                     traverse(child_parent, child_path, paths, node_counter);
+    ///                 }
+    ///             }
+    ///         }
+    ///     }
+    ///     
+    ///     let mut paths = Vec::new();
+    ///     traverse(tree.root(), "Node0".to_string(), &mut paths, &mut node_counter);
+    ///     paths
+    /// }
+    ///
+    /// // Bottom-up traversal: Aggregate child envelopes into parents (post-order)
     /// // NB: this is a contrived example showing manual envelope aggregation
     /// fn aggregate_envelopes_bottom_up(tree: &RTree<PointWithLabel>) -> Vec<(String, f32, f32)> {
     ///     use rstar::{Envelope, AABB, RTreeObject};
@@ -709,6 +1315,7 @@ where
     /// iterator that successively removes selected elements and returns
     /// them. This is the most generic drain API, see also:
     /// [`RTree::drain_in_envelope_intersecting`],
+    /// [`RTree::drain_containing_envelope`],
     /// [`RTree::drain_within_distance`].
     ///
     /// # Remarks
@@ -737,6 +1344,17 @@ where
         let selection_function = SelectInEnvelopeFuncIntersecting::new(envelope);
         self.drain_with_selection_function(selection_function)
     }
+
+    /// Drains elements whose envelope contains the `envelope`. Similar to
+    /// `locate_containing_envelope`, except the elements are removed
+    /// and returned via an iterator.
+    pub fn drain_containing_envelope(
+        &mut self,
+        envelope: T::Envelope,
+    ) -> DrainIterator<'_, T, SelectContainingEnvelopeFunction<T>, Params> {
+        let selection_function = SelectContainingEnvelopeFunction::new(envelope);
+        self.drain_with_selection_function(selection_function)
+    }
 }
 
 impl<T, Params> RTree<T, Params>
@@ -814,6 +1432,50 @@ where
         LocateAllAtPointMut::new(&mut self.root, SelectAtPointFunction::new(point))
     }
 
+    /// Locate all elements containing each of the given points.
+    ///
+    /// Returns one `Vec` per query point, in the same order as `points`. This is
+    /// equivalent to calling [RTree::locate_all_at_point] for each point but sorts the
+    /// queries spatially and shares the traversal of the tree between nearby queries.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// use rstar::primitives::Rectangle;
+    ///
+    /// let tree = RTree::bulk_load(vec![
+    ///   Rectangle::from_corners([0.0, 0.0], [2.0, 2.0]),
+    ///   Rectangle::from_corners([1.0, 1.0], [3.0, 3.0])
+    /// ]);
+    ///
+    /// let located = tree.locate_all_at_points(&[[1.5, 1.5], [0.0, 0.0], [-1., 0.0]]);
+    /// assert_eq!(located[0].len(), 2);
+    /// assert_eq!(located[1].len(), 1);
+    /// assert!(located[2].is_empty());
+    /// ```
+    pub fn locate_all_at_points(
+        &self,
+        points: &[<T::Envelope as Envelope>::Point],
+    ) -> Vec<Vec<&T>> {
+        batch::locate_all_at_points(&self.root, points)
+    }
+
+    /// Parallel variant of [`locate_all_at_points`][Self::locate_all_at_points].
+    ///
+    /// The spatially sorted queries are split into chunks which are processed in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn par_locate_all_at_points(
+        &self,
+        points: &[<T::Envelope as Envelope>::Point],
+    ) -> Vec<Vec<&T>>
+    where
+        T: Sync,
+        T::Envelope: Sync,
+        <T::Envelope as Envelope>::Point: Sync,
+    {
+        batch::par_locate_all_at_points(&self.root, points)
+    }
+
     /// Variant of [`locate_all_at_point`][Self::locate_all_at_point] using internal iteration.
     pub fn locate_all_at_point_int<'a, V, B>(
         &'a self,
@@ -961,16 +1623,60 @@ where
         &self,
         query_point: <T::Envelope as Envelope>::Point,
     ) -> Option<(&T, Distance<T>)> {
+        self.nearest_neighbor_to_with_distance_2(query_point)
+    }
+
+    /// Returns the nearest neighbor for each of the given points.
+    ///
+    /// Returns one result per query point, in the same order as `query_points`. This
+    /// is equivalent to calling [RTree::nearest_neighbor] for each point but sorts the
+    /// queries spatially. Each query still searches the tree from its root, but the
+    /// neighbor of the previous query bounds the search of the next one, which avoids
+    /// visiting most nodes for nearby queries.
+    ///
+    /// If several elements have the same distance to a query point, any of them
+    /// may be returned.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// let tree = RTree::bulk_load(vec![
+    ///   [0.0, 0.0],
+    ///   [0.0, 1.0],
+    /// ]);
+    /// assert_eq!(
+    ///     tree.nearest_neighbors_batch(&[[-1., 0.0], [0.0, 2.0]]),
+    ///     vec![Some(&[0.0, 0.0]), Some(&[0.0, 1.0])]
+    /// );
+    /// ```
+    pub fn nearest_neighbors_batch(
+        &self,
+        query_points: &[<T::Envelope as Envelope>::Point],
+    ) -> Vec<Option<&T>> {
         if self.size > 0 {
-            // The single-nearest-neighbor retrieval may in rare cases return None due to
-            // rounding issues. The iterator will still work, though.
-            nearest_neighbor::nearest_neighbor_with_distance_2(&self.root, query_point.clone())
-                .or_else(|| {
-                    self.nearest_neighbor_iter_with_distance_2(query_point)
-                        .next()
-                })
+            batch::nearest_neighbors_batch(&self.root, query_points)
         } else {
-            None
+            vec![None; query_points.len()]
+        }
+    }
+
+    /// Parallel variant of [`nearest_neighbors_batch`][Self::nearest_neighbors_batch].
+    ///
+    /// The spatially sorted queries are split into chunks which are processed in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn par_nearest_neighbors_batch(
+        &self,
+        query_points: &[<T::Envelope as Envelope>::Point],
+    ) -> Vec<Option<&T>>
+    where
+        T: Sync,
+        T::Envelope: Sync,
+        <T::Envelope as Envelope>::Point: Sync,
+    {
+        if self.size > 0 {
+            batch::par_nearest_neighbors_batch(&self.root, query_points)
+        } else {
+            vec![None; query_points.len()]
         }
     }
 
@@ -1042,6 +1748,102 @@ where
         nearest_neighbor::nearest_neighbors_with_distance_2(&self.root, query_point.clone())
     }
 
+    /// Returns an approximate nearest neighbor for a given point.
+    ///
+    /// The returned element's distance to `query_point` is at most `1 + epsilon` times the
+    /// distance of the true nearest neighbor. Larger values of `epsilon` allow the search
+    /// to skip more nodes, which can speed up queries considerably, especially for
+    /// high-dimensional trees. An `epsilon` of zero returns an exact nearest neighbor.
+    /// `epsilon` must not be negative.
+    ///
+    /// The distance is calculated by calling [PointDistance::distance_2].
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// let tree = RTree::bulk_load(vec![
+    ///   [0.0, 0.0],
+    ///   [0.0, 1.0],
+    /// ]);
+    /// assert_eq!(tree.nearest_neighbor_approx([0.0, 0.2], 0.0), Some(&[0.0, 0.0]));
+    ///
+    /// // Both elements lie within 1.5 times the nearest distance
+    /// let neighbor = tree.nearest_neighbor_approx([0.0, 0.45], 0.5).unwrap();
+    /// assert!(neighbor == &[0.0, 0.0] || neighbor == &[0.0, 1.0]);
+    /// ```
+    pub fn nearest_neighbor_approx(
+        &self,
+        query_point: <T::Envelope as Envelope>::Point,
+        epsilon: Distance<T>,
+    ) -> Option<&T> {
+        self.k_nearest_neighbors_approx(query_point, 1, epsilon)
+            .pop()
+    }
+
+    /// Returns up to `k` approximate nearest neighbors for a given point, sorted by their
+    /// distance.
+    ///
+    /// The distance of the `i`-th returned element is at most `1 + epsilon` times the
+    /// distance of the true `i`-th nearest neighbor. `epsilon` must not be negative.
+    /// See [nearest_neighbor_approx](Self::nearest_neighbor_approx) for more information.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// let tree = RTree::bulk_load(vec![
+    ///   [0.0, 0.0],
+    ///   [0.0, 1.0],
+    ///   [0.0, 3.0],
+    /// ]);
+    /// let neighbors = tree.k_nearest_neighbors_approx([0.0, 0.2], 2, 0.0);
+    /// assert_eq!(neighbors, vec![&[0.0, 0.0], &[0.0, 1.0]]);
+    /// ```
+    pub fn k_nearest_neighbors_approx(
+        &self,
+        query_point: <T::Envelope as Envelope>::Point,
+        k: usize,
+        epsilon: Distance<T>,
+    ) -> Vec<&T> {
+        let factor = Distance::<T>::one() + epsilon;
+        nearest_neighbor::k_nearest_neighbors_approx(&self.root, query_point, k, factor * factor)
+            .into_iter()
+            .map(|(neighbor, _)| neighbor)
+            .collect()
+    }
+
+    /// Returns all elements that have a given point among their `k` nearest neighbors.
+    ///
+    /// An element is returned if less than `k` _other_ elements of the tree are strictly
+    /// closer to it than `query_point`. This answers questions like "which customers would
+    /// have a new store among their `k` nearest stores?".
+    ///
+    /// Distances between elements are measured from their [position](PointObject::position),
+    /// which limits this method to trees containing points. Subtrees are pruned by previously
+    /// found candidates, each of which is then verified by counting the elements within its
+    /// distance to `query_point`. The order of the returned elements is unspecified.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// let customers = RTree::bulk_load(vec![
+    ///   [0.0, 0.0],
+    ///   [1.0, 0.0],
+    ///   [10.0, 0.0],
+    /// ]);
+    ///
+    /// let new_store = [2.0, 0.0];
+    /// let mut customers_nearby = customers.reverse_nearest_neighbors(new_store, 1);
+    /// customers_nearby.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    /// assert_eq!(customers_nearby, vec![&[1.0, 0.0], &[10.0, 0.0]]);
+    /// ```
+    pub fn reverse_nearest_neighbors<P>(&self, query_point: P, k: usize) -> Vec<&T>
+    where
+        P: Point,
+        T: PointObject + RTreeObject<Envelope = AABB<P>>,
+    {
+        reverse_nearest_neighbor::reverse_nearest_neighbors(&self.root, query_point, k)
+    }
+
     /// Returns all elements of the tree within a certain distance.
     ///
     /// The elements may be returned in any order. Each returned element
@@ -1059,6 +1861,57 @@ where
         LocateWithinDistanceIterator::new(self.root(), selection_function)
     }
 
+    /// Returns all elements of the tree within a certain distance, sorted by their distance.
+    ///
+    /// Yields `(element, distance^2)` tuples in ascending order of their squared distance,
+    /// which is less or equal to the given squared distance. Unlike
+    /// [nearest_neighbor_iter_with_distance_2](#method.nearest_neighbor_iter_with_distance_2),
+    /// nodes farther away than the given distance are never visited or stored.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// let tree = RTree::bulk_load(vec![
+    ///   [0.0, 0.0],
+    ///   [2.0, 0.0],
+    ///   [1.0, 0.0],
+    ///   [10.0, 0.0],
+    /// ]);
+    /// let nearby: Vec<_> = tree.locate_within_distance_sorted([0.25, 0.0], 2.0 * 2.0).collect();
+    /// assert_eq!(
+    ///     nearby,
+    ///     vec![(&[0.0, 0.0], 0.0625), (&[1.0, 0.0], 0.5625), (&[2.0, 0.0], 3.0625)]
+    /// );
+    /// ```
+    pub fn locate_within_distance_sorted(
+        &self,
+        query_point: <T::Envelope as Envelope>::Point,
+        max_squared_radius: Distance<T>,
+    ) -> LocateWithinDistanceSortedIterator<'_, T> {
+        LocateWithinDistanceSortedIterator::new(&self.root, query_point, max_squared_radius)
+    }
+
+    /// Returns `true` if any element lies within a certain distance.
+    ///
+    /// This is equivalent to `locate_within_distance(..).next().is_some()` but stops
+    /// at the first hit without allocating.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// let tree = RTree::bulk_load(vec![[0.0, 0.0], [3.0, 4.0]]);
+    /// assert!(tree.any_within_distance([3.0, 0.0], 4.0 * 4.0));
+    /// assert!(!tree.any_within_distance([3.0, 0.0], 2.0 * 2.0));
+    /// ```
+    pub fn any_within_distance(
+        &self,
+        query_point: <T::Envelope as Envelope>::Point,
+        max_squared_radius: Distance<T>,
+    ) -> bool {
+        let selection_function = SelectWithinDistanceFunction::new(query_point, max_squared_radius);
+        any_selected(&self.root, &selection_function)
+    }
+
     /// Drain all elements of the tree within a certain distance.
     ///
     /// Similar to [`RTree::locate_within_distance`], but removes and
@@ -1110,6 +1963,73 @@ where
         nearest_neighbor::NearestNeighborDistance2Iterator::new(&self.root, query_point)
     }
 
+    /// Returns the farthest neighbor for a given point.
+    ///
+    /// The distance is calculated by calling
+    /// [PointDistance::distance_2]. Returns `None` if the tree is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// let tree = RTree::bulk_load(vec![
+    ///   [0.0, 0.0],
+    ///   [0.0, 1.0],
+    ///   [3.0, 0.0],
+    /// ]);
+    /// assert_eq!(tree.farthest_neighbor([0.0, 0.5]), Some(&[3.0, 0.0]));
+    /// ```
+    pub fn farthest_neighbor<P>(&self, query_point: P) -> Option<&T>
+    where
+        P: Point,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        self.farthest_neighbor_iter(query_point).next()
+    }
+
+    /// Returns all elements of the tree sorted by their distance to a given point,
+    /// the farthest element coming first.
+    ///
+    /// # Runtime
+    /// Every `next()` call runs in `O(log(n))`. Creating the iterator runs in
+    /// `O(log(n))`.
+    /// The [r-tree documentation](RTree) contains more information about
+    /// r-tree performance.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// let tree = RTree::bulk_load(vec![
+    ///   [0.0, 0.0],
+    ///   [0.0, 1.0],
+    /// ]);
+    ///
+    /// let farthest_neighbors = tree.farthest_neighbor_iter([0.0, 0.0]).collect::<Vec<_>>();
+    /// assert_eq!(farthest_neighbors, vec![&[0.0, 1.0], &[0.0, 0.0]]);
+    /// ```
+    pub fn farthest_neighbor_iter<P>(&self, query_point: P) -> FarthestNeighborIterator<'_, T>
+    where
+        P: Point,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        FarthestNeighborIterator::new(&self.root, query_point)
+    }
+
+    /// Returns `(element, distance^2)` tuples of the tree sorted by their distance to a given
+    /// point, the farthest element coming first.
+    ///
+    /// The distance is calculated by calling
+    /// [PointDistance::distance_2].
+    pub fn farthest_neighbor_iter_with_distance_2<P>(
+        &self,
+        query_point: P,
+    ) -> FarthestNeighborDistance2Iterator<'_, T>
+    where
+        P: Point,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        FarthestNeighborDistance2Iterator::new(&self.root, query_point)
+    }
+
     /// Returns `(element, distance^2)` tuples of the tree sorted by their distance to a given point.
     ///
     /// The distance is calculated by calling
@@ -1121,6 +2041,113 @@ where
         nearest_neighbor::NearestNeighborDistance2Iterator::new(&self.root, query_point)
     }
 
+    /// Returns the nearest neighbor for a given point among all elements matching a predicate.
+    ///
+    /// The predicate is evaluated while searching the tree, which is more efficient than
+    /// calling [`Iterator::find`] on [RTree::nearest_neighbor_iter]: rejected elements are
+    /// never added to the search queue. To additionally skip whole subtrees that are known
+    /// not to contain any match, use
+    /// [`nearest_neighbor_with_selection_function`](Self::nearest_neighbor_with_selection_function).
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// let tree = RTree::bulk_load(vec![
+    ///   [0.0, 0.0],
+    ///   [0.0, 1.0],
+    ///   [2.0, 0.0],
+    /// ]);
+    /// let nearest = tree.nearest_neighbor_filtered([0.0, 0.0], |point| point[0] > 1.0);
+    /// assert_eq!(nearest, Some(&[2.0, 0.0]));
+    /// ```
+    pub fn nearest_neighbor_filtered<F>(
+        &self,
+        query_point: <T::Envelope as Envelope>::Point,
+        predicate: F,
+    ) -> Option<&T>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.nearest_neighbor_iter_filtered(query_point, predicate)
+            .next()
+    }
+
+    /// Returns all elements matching a predicate sorted by their distance to a given point.
+    ///
+    /// See [RTree::nearest_neighbor_filtered] for more information.
+    pub fn nearest_neighbor_iter_filtered<F>(
+        &self,
+        query_point: <T::Envelope as Envelope>::Point,
+        predicate: F,
+    ) -> NearestNeighborFilteredIterator<'_, T, F>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.nearest_neighbor_iter_with_selection_function(
+            query_point,
+            SelectByPredicateFunction::new(predicate),
+        )
+    }
+
+    /// Returns the nearest neighbor for a given point among all elements selected by a
+    /// [`SelectionFunction`].
+    ///
+    /// Parent nodes rejected by [`SelectionFunction::should_unpack_parent`] are pruned
+    /// from the search. Leaves are only returned if
+    /// [`SelectionFunction::should_unpack_leaf`] returns `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::{RTree, SelectionFunction, AABB};
+    ///
+    /// // Selects points on the right half plane, skipping all nodes on the left
+    /// struct RightHalfPlane;
+    ///
+    /// impl SelectionFunction<[f64; 2]> for RightHalfPlane {
+    ///     fn should_unpack_parent(&self, envelope: &AABB<[f64; 2]>) -> bool {
+    ///         envelope.upper()[0] > 0.0
+    ///     }
+    ///
+    ///     fn should_unpack_leaf(&self, point: &[f64; 2]) -> bool {
+    ///         point[0] > 0.0
+    ///     }
+    /// }
+    ///
+    /// let tree = RTree::bulk_load(vec![[-1.0, 0.0], [2.0, 0.0], [3.0, 3.0]]);
+    /// let nearest = tree.nearest_neighbor_with_selection_function([0.0, 0.0], RightHalfPlane);
+    /// assert_eq!(nearest, Some(&[2.0, 0.0]));
+    /// ```
+    pub fn nearest_neighbor_with_selection_function<S>(
+        &self,
+        query_point: <T::Envelope as Envelope>::Point,
+        selection_function: S,
+    ) -> Option<&T>
+    where
+        S: SelectionFunction<T>,
+    {
+        self.nearest_neighbor_iter_with_selection_function(query_point, selection_function)
+            .next()
+    }
+
+    /// Returns all elements selected by a [`SelectionFunction`] sorted by their distance
+    /// to a given point.
+    ///
+    /// See [RTree::nearest_neighbor_with_selection_function] for more information.
+    pub fn nearest_neighbor_iter_with_selection_function<S>(
+        &self,
+        query_point: <T::Envelope as Envelope>::Point,
+        selection_function: S,
+    ) -> NearestNeighborIterator<'_, T, <T::Envelope as Envelope>::Point, S>
+    where
+        S: SelectionFunction<T>,
+    {
+        nearest_neighbor::NearestNeighborIterator::new_with_selection_function(
+            &self.root,
+            query_point,
+            selection_function,
+        )
+    }
+
     /// Removes the nearest neighbor for a given point and returns it.
     ///
     /// The distance is calculated by calling
@@ -1150,6 +2177,218 @@ where
     }
 }
 
+impl<T, Params> RTree<T, Params>
+where
+    Params: RTreeParams,
+    T: RTreeObject,
+{
+    /// Returns the nearest neighbor for a given query geometry.
+    ///
+    /// In contrast to [RTree::nearest_neighbor], the query is not restricted to a
+    /// point: any [NearestNeighborQuery], e.g. a [Line](crate::primitives::Line) or an
+    /// [AABB](crate::AABB), can be used to find the closest object.
+    ///
+    /// The distance is calculated by calling
+    /// [NearestNeighborQuery::object_distance_2].
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// use rstar::primitives::Line;
+    ///
+    /// let tree = RTree::bulk_load(vec![
+    ///   [0.0, 0.0],
+    ///   [0.0, 1.0],
+    ///   [3.0, 3.0],
+    /// ]);
+    /// let segment = Line::new([2.0, 4.0], [4.0, 2.0]);
+    /// assert_eq!(tree.nearest_neighbor_to(segment), Some(&[3.0, 3.0]));
+    /// ```
+    pub fn nearest_neighbor_to<Q>(&self, query: Q) -> Option<&T>
+    where
+        Q: NearestNeighborQuery<T>,
+    {
+        self.nearest_neighbor_to_with_distance_2(query)
+            .map(|(neighbor, _)| neighbor)
+    }
+
+    /// Returns the nearest neighbor for a given query geometry with distance squared.
+    ///
+    /// The distance is calculated by calling
+    /// [NearestNeighborQuery::object_distance_2].
+    pub fn nearest_neighbor_to_with_distance_2<Q>(&self, query: Q) -> Option<(&T, Distance<T>)>
+    where
+        Q: NearestNeighborQuery<T>,
+    {
+        if self.size > 0 {
+            // The single-nearest-neighbor retrieval may in rare cases return None due to
+            // rounding issues. The iterator will still work, though.
+            nearest_neighbor::nearest_neighbor_with_distance_2(&self.root, &query)
+                .or_else(|| self.nearest_neighbor_iter_to_with_distance_2(query).next())
+        } else {
+            None
+        }
+    }
+
+    /// Returns all elements of the tree sorted by their distance to a given query geometry.
+    ///
+    /// See [RTree::nearest_neighbor_to] for more information about query geometries.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::{RTree, AABB};
+    /// let tree = RTree::bulk_load(vec![
+    ///   [0.0, 0.5],
+    ///   [0.0, 4.0],
+    ///   [2.0, 1.5],
+    /// ]);
+    ///
+    /// let query = AABB::from_corners([0.0, 1.0], [1.0, 2.0]);
+    /// let nearest_neighbors = tree.nearest_neighbor_iter_to(query).collect::<Vec<_>>();
+    /// assert_eq!(nearest_neighbors, vec![&[0.0, 0.5], &[2.0, 1.5], &[0.0, 4.0]]);
+    /// ```
+    pub fn nearest_neighbor_iter_to<Q>(&self, query: Q) -> NearestNeighborIterator<'_, T, Q>
+    where
+        Q: NearestNeighborQuery<T>,
+    {
+        nearest_neighbor::NearestNeighborIterator::new(&self.root, query)
+    }
+
+    /// Returns `(element, distance^2)` tuples of the tree sorted by their distance to a given
+    /// query geometry.
+    ///
+    /// The distance is calculated by calling
+    /// [NearestNeighborQuery::object_distance_2].
+    pub fn nearest_neighbor_iter_to_with_distance_2<Q>(
+        &self,
+        query: Q,
+    ) -> NearestNeighborDistance2Iterator<'_, T, Q>
+    where
+        Q: NearestNeighborQuery<T>,
+    {
+        nearest_neighbor::NearestNeighborDistance2Iterator::new(&self.root, query)
+    }
+
+    /// Returns all elements of the tree within a certain distance measured by a [Metric].
+    ///
+    /// The elements may be returned in any order. Each returned element's
+    /// [position](PointObject::position) has a distance less or equal to `max_distance`.
+    /// Note that some metrics, e.g. [WeightedEuclidean](crate::metric::WeightedEuclidean),
+    /// measure _squared_ distances.
+    ///
+    /// A metric only measures distances between points, which limits this method to trees
+    /// containing points. Use [locate_within_distance](Self::locate_within_distance) to
+    /// find other objects by their euclidean distance.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// use rstar::metric::Manhattan;
+    ///
+    /// let tree = RTree::bulk_load(vec![[1.0, 1.0], [2.0, 0.5], [0.0, 3.0]]);
+    /// let within: Vec<_> = tree.locate_within_metric_distance([0.0, 0.0], 2.0, Manhattan).collect();
+    /// assert_eq!(within, vec![&[1.0, 1.0]]);
+    /// ```
+    pub fn locate_within_metric_distance<P, M>(
+        &self,
+        query_point: P,
+        max_distance: P::Scalar,
+        metric: M,
+    ) -> LocateWithinMetricDistanceIterator<'_, T, P, M>
+    where
+        P: Point,
+        M: Metric<P>,
+        T: PointObject + RTreeObject<Envelope = AABB<P>>,
+    {
+        let selection_function =
+            SelectWithinMetricDistanceFunction::new(query_point, max_distance, metric);
+        LocateWithinMetricDistanceIterator::new(self.root(), selection_function)
+    }
+
+    /// Returns `(element, distance^2)` tuples of the tree sorted by their distance to a given
+    /// point within a periodic domain.
+    ///
+    /// The domain wraps around after `extent` along each axis. Distances are measured
+    /// between the closest periodic images of the query point and each element's envelope,
+    /// see [Periodic](crate::metric::Periodic). This is exact for trees containing points.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    ///
+    /// let particles = RTree::bulk_load(vec![[1.0, 5.0], [9.5, 5.0]]);
+    /// let extent = [10.0, 10.0];
+    /// let neighbors: Vec<_> = particles
+    ///     .nearest_neighbor_iter_with_distance_2_periodic([0.0, 5.0], extent)
+    ///     .collect();
+    /// assert_eq!(neighbors, vec![(&[9.5, 5.0], 0.25), (&[1.0, 5.0], 1.0)]);
+    /// ```
+    pub fn nearest_neighbor_iter_with_distance_2_periodic<P>(
+        &self,
+        query_point: P,
+        extent: P,
+    ) -> NearestNeighborDistance2Iterator<'_, T, MetricQuery<P, Periodic<P>>>
+    where
+        P: Point,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        self.nearest_neighbor_iter_to_with_distance_2(MetricQuery::new(
+            query_point,
+            Periodic::new(extent),
+        ))
+    }
+
+    /// Returns all elements of the tree within a certain distance of a point within a
+    /// periodic domain.
+    ///
+    /// The domain wraps around after `extent` along each axis. An element is returned if
+    /// the squared distance between the closest periodic images of the query point and its
+    /// [position](PointObject::position) is less or equal to `max_squared_radius`.
+    pub fn locate_within_distance_periodic<P>(
+        &self,
+        query_point: P,
+        max_squared_radius: P::Scalar,
+        extent: P,
+    ) -> LocateWithinMetricDistanceIterator<'_, T, P, Periodic<P>>
+    where
+        P: Point,
+        T: PointObject + RTreeObject<Envelope = AABB<P>>,
+    {
+        self.locate_within_metric_distance(query_point, max_squared_radius, Periodic::new(extent))
+    }
+
+    /// Locates elements intersecting an envelope within a periodic domain.
+    ///
+    /// The domain wraps around after `extent` along each axis. The envelope may extend
+    /// beyond the domain's boundaries, e.g. an envelope from `-1` to `1` with an extent
+    /// of `10` also covers elements between `9` and `10`. All periodic images of the envelope
+    /// are checked within a single traversal and each element is returned at most once.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::{RTree, AABB};
+    ///
+    /// let particles = RTree::bulk_load(vec![[0.5, 0.5], [9.5, 9.5], [5.0, 5.0]]);
+    /// let envelope = AABB::from_corners([-1.0, -1.0], [1.0, 1.0]);
+    /// let extent = [10.0, 10.0];
+    /// assert_eq!(particles.locate_in_envelope_intersecting_periodic(envelope, extent).count(), 2);
+    /// ```
+    pub fn locate_in_envelope_intersecting_periodic<P>(
+        &self,
+        envelope: AABB<P>,
+        extent: P,
+    ) -> LocateInEnvelopeIntersectingPeriodic<'_, T, P>
+    where
+        P: Point,
+        T: RTreeObject<Envelope = AABB<P>>,
+    {
+        LocateInEnvelopeIntersectingPeriodic::new(
+            &self.root,
+            SelectPeriodicEnvelopeIntersectingFunction::new(envelope, extent),
+        )
+    }
+}
+
 impl<T, Params> RTree<T, Params>
 where
     T: RTreeObject,
@@ -1169,6 +2408,136 @@ where
     }
 }
 
+impl<T, P, S, Params> RTree<Temporal<T, S>, Params>
+where
+    Params: RTreeParams,
+    S: RTreeNum,
+    P: Point<Scalar = S>,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    /// Returns all elements contained within a spatial envelope that are valid at a given time.
+    ///
+    /// An element is valid at `time` if `valid_from <= time < valid_to`, see
+    /// [Temporal]. The search prunes nodes by their spatial and temporal bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::{RTree, AABB};
+    /// use rstar::primitives::Temporal;
+    ///
+    /// let tree = RTree::bulk_load(vec![
+    ///     Temporal::new([0.0, 0.0], 0.0, 10.0),
+    ///     Temporal::new([1.0, 1.0], 5.0, 15.0),
+    ///     Temporal::new([5.0, 5.0], 0.0, 20.0),
+    /// ]);
+    /// let envelope = AABB::from_corners([0.0, 0.0], [2.0, 2.0]);
+    /// assert_eq!(tree.locate_in_envelope_at(envelope, 2.0).count(), 1);
+    /// assert_eq!(tree.locate_in_envelope_at(envelope, 7.0).count(), 2);
+    /// assert_eq!(tree.locate_in_envelope_at(envelope, 10.0).count(), 1);
+    /// ```
+    pub fn locate_in_envelope_at(
+        &self,
+        envelope: AABB<P>,
+        time: S,
+    ) -> LocateInEnvelopeAt<'_, T, P> {
+        LocateInEnvelopeAt::new(&self.root, SelectInEnvelopeAtFunction::new(envelope, time))
+    }
+
+    /// Returns the nearest neighbor of a point among all elements valid at a given time.
+    ///
+    /// The distance is calculated by calling [PointDistance::distance_2] on the
+    /// wrapped objects.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// use rstar::primitives::Temporal;
+    ///
+    /// let tree = RTree::bulk_load(vec![
+    ///     Temporal::new([0.0, 0.0], 0.0, 10.0),
+    ///     Temporal::new([5.0, 5.0], 0.0, 20.0),
+    /// ]);
+    /// assert_eq!(tree.nearest_neighbor_at([1.0, 1.0], 5.0).unwrap().object(), &[0.0, 0.0]);
+    /// assert_eq!(tree.nearest_neighbor_at([1.0, 1.0], 15.0).unwrap().object(), &[5.0, 5.0]);
+    /// assert_eq!(tree.nearest_neighbor_at([1.0, 1.0], 25.0), None);
+    /// ```
+    pub fn nearest_neighbor_at(&self, point: P, time: S) -> Option<&Temporal<T, S>>
+    where
+        T: PointDistance,
+    {
+        NearestNeighborIterator::new_with_selection_function(
+            &self.root,
+            SpatialQuery(point),
+            SelectValidAtFunction::new(time),
+        )
+        .next()
+    }
+}
+
+impl<T, P, Params> RTree<Moving<T, P>, Params>
+where
+    Params: RTreeParams,
+    P: Point,
+    T: RTreeObject<Envelope = AABB<P>>,
+{
+    /// Returns all moving elements whose envelope intersects a given envelope at a given time.
+    ///
+    /// See [Moving] for more information. The search prunes nodes by the region their
+    /// time-parameterized envelope covers at `time`.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::{RTree, AABB};
+    /// use rstar::primitives::Moving;
+    ///
+    /// let tree = RTree::bulk_load(vec![
+    ///     Moving::new([0.0, 0.0], [1.0, 1.0]),
+    ///     Moving::new([4.0, 4.0], [0.0, 0.0]),
+    /// ]);
+    /// let envelope = AABB::from_corners([3.0, 3.0], [5.0, 5.0]);
+    /// assert_eq!(tree.locate_in_envelope_intersecting_at(envelope, 0.0).count(), 1);
+    /// assert_eq!(tree.locate_in_envelope_intersecting_at(envelope, 4.0).count(), 2);
+    /// ```
+    pub fn locate_in_envelope_intersecting_at(
+        &self,
+        envelope: AABB<P>,
+        time: P::Scalar,
+    ) -> LocateInEnvelopeIntersectingAt<'_, T, P> {
+        LocateInEnvelopeIntersectingAt::new(
+            &self.root,
+            SelectMovingEnvelopeIntersectingFunction::new(envelope, time),
+        )
+    }
+
+    /// Returns all moving elements sorted by their distance to a point at a given time.
+    ///
+    /// The distance to an element is measured to its
+    /// [envelope at the given time](Moving::envelope_at), which is exact for moving points.
+    /// Use [Iterator::take] to retrieve the `k` nearest neighbors.
+    ///
+    /// # Example
+    /// ```
+    /// use rstar::RTree;
+    /// use rstar::primitives::Moving;
+    ///
+    /// let tree = RTree::bulk_load(vec![
+    ///     Moving::new([0.0, 0.0], [1.0, 0.0]),
+    ///     Moving::new([5.0, 0.0], [0.0, 0.0]),
+    /// ]);
+    /// let nearest = tree.nearest_neighbor_iter_at([0.0, 0.0], 0.0).next().unwrap();
+    /// assert_eq!(nearest.object(), &[0.0, 0.0]);
+    /// let nearest = tree.nearest_neighbor_iter_at([10.0, 0.0], 10.0).next().unwrap();
+    /// assert_eq!(nearest.object(), &[0.0, 0.0]);
+    /// ```
+    pub fn nearest_neighbor_iter_at(
+        &self,
+        point: P,
+        time: P::Scalar,
+    ) -> NearestNeighborIterator<'_, Moving<T, P>, PointAtTime<P>> {
+        NearestNeighborIterator::new(&self.root, PointAtTime::new(point, time))
+    }
+}
+
 impl<T, Params> IntoIterator for RTree<T, Params>
 where
     T: RTreeObject,